@@ -0,0 +1,407 @@
+use crate::core::drawing::{
+    fill_path_transformed_mut, stroke_path_transformed_mut, Canvas, Clip, Clipped, Composite,
+    FillRule, LayerStack, Mask,
+};
+use crate::core::entity::definitions::Clamp;
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+use crate::core::entity::transform::Transform2D;
+use crate::core::operation::composite::BlendMode;
+use crate::core::operation::premultiply::unpremultiply;
+use crate::core::paint::{Paint, Solid};
+use crate::core::path::{Path, StrokeStyle};
+use crate::core::text::{measure, text_outline, FontCollection, TextAnchor};
+use conv::ValueInto;
+use image::Pixel;
+use rusttype::{Font, Scale};
+use std::rc::Rc;
+
+#[derive(Clone)]
+struct GraphicsState<P> {
+    transform: Transform2D,
+    clip: Option<Rc<Clip>>,
+    fill_paint: Rc<dyn Paint<P>>,
+    stroke_paint: Rc<dyn Paint<P>>,
+    stroke_style: StrokeStyle,
+    global_alpha: f32,
+    blend_mode: BlendMode,
+    fonts: FontCollection<'static>,
+    font_scale: Scale,
+    text_anchor: TextAnchor,
+}
+
+impl<P> GraphicsState<P> {
+    fn text_outline(&self, text: &str, x: f32, y: f32) -> Option<Path> {
+        let fonts = &self.fonts;
+        let scale = self.font_scale;
+        let v_metrics = fonts.primary()?.v_metrics(scale);
+        let (dx, dy) = self.text_anchor.offset(
+            measure(scale, fonts, text),
+            v_metrics.ascent,
+            v_metrics.descent,
+        );
+        Some(text_outline(scale, fonts, text, Point::new(x + dx, y + dy)))
+    }
+}
+
+pub struct Context<C: Canvas> {
+    canvas: LayerStack<C>,
+    state: GraphicsState<C::Pixel>,
+    stack: Vec<GraphicsState<C::Pixel>>,
+}
+
+impl<C> Context<C>
+where
+    C: Canvas,
+    C::Pixel: 'static,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    pub fn new(canvas: C) -> Context<C> {
+        let black: Rc<dyn Paint<C::Pixel>> =
+            Rc::new(Solid(unpremultiply::<C::Pixel>([0.0, 0.0, 0.0, 1.0])));
+        Context {
+            canvas: LayerStack::new(canvas),
+            state: GraphicsState {
+                transform: Transform2D::identity(),
+                clip: None,
+                fill_paint: black.clone(),
+                stroke_paint: black,
+                stroke_style: StrokeStyle::default(),
+                global_alpha: 1.0,
+                blend_mode: BlendMode::SourceOver,
+                fonts: FontCollection::new(),
+                font_scale: Scale::uniform(10.0),
+                text_anchor: TextAnchor::default(),
+            },
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn canvas(&self) -> &C {
+        self.canvas.canvas()
+    }
+
+    pub fn canvas_mut(&mut self) -> &mut C {
+        self.canvas.canvas_mut()
+    }
+
+    pub fn into_inner(self) -> C {
+        self.canvas.into_inner()
+    }
+
+    pub fn save(&mut self) {
+        self.stack.push(self.state.clone());
+    }
+
+    pub fn restore(&mut self) {
+        if let Some(state) = self.stack.pop() {
+            self.state = state;
+        }
+    }
+
+    pub fn transform(&self) -> Transform2D {
+        self.state.transform
+    }
+
+    pub fn set_transform(&mut self, transform: Transform2D) {
+        self.state.transform = transform;
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.state.transform = Transform2D::identity();
+    }
+
+    pub fn apply_transform(&mut self, transform: &Transform2D) {
+        self.state.transform = transform.then(&self.state.transform);
+    }
+
+    pub fn translate(&mut self, tx: f32, ty: f32) {
+        self.apply_transform(&Transform2D::translate(tx, ty));
+    }
+
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.apply_transform(&Transform2D::scale(sx, sy));
+    }
+
+    pub fn rotate(&mut self, angle: f32) {
+        self.apply_transform(&Transform2D::rotate(angle));
+    }
+
+    pub fn clip(&self) -> Option<&Clip> {
+        self.state.clip.as_deref()
+    }
+
+    pub fn clip_path(&mut self, path: &Path, fill_rule: FillRule) {
+        let clip = match self.canvas_bounds() {
+            Some(bounds) => {
+                Clip::from_path(&path.transform(&self.state.transform), fill_rule, bounds)
+            }
+            None => Clip::empty(),
+        };
+        let clip = match &self.state.clip {
+            Some(current) => current.intersect(&clip),
+            None => clip,
+        };
+        self.state.clip = Some(Rc::new(clip));
+    }
+
+    pub fn clip_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let mut path = Path::new();
+        path.add_rect(x, y, width, height);
+        self.clip_path(&path, FillRule::NonZero);
+    }
+
+    pub fn set_fill_paint<T: Paint<C::Pixel> + 'static>(&mut self, paint: T) {
+        self.state.fill_paint = Rc::new(paint);
+    }
+
+    pub fn set_stroke_paint<T: Paint<C::Pixel> + 'static>(&mut self, paint: T) {
+        self.state.stroke_paint = Rc::new(paint);
+    }
+
+    pub fn stroke_style(&self) -> &StrokeStyle {
+        &self.state.stroke_style
+    }
+
+    pub fn set_stroke_style(&mut self, style: StrokeStyle) {
+        self.state.stroke_style = style;
+    }
+
+    pub fn set_line_width(&mut self, width: f32) {
+        self.state.stroke_style.width = width;
+    }
+
+    pub fn global_alpha(&self) -> f32 {
+        self.state.global_alpha
+    }
+
+    pub fn set_global_alpha(&mut self, alpha: f32) {
+        self.state.global_alpha = alpha.clamp(0.0, 1.0);
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.state.blend_mode
+    }
+
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.state.blend_mode = mode;
+    }
+
+    pub fn font(&self) -> Option<&Font<'static>> {
+        self.state.fonts.primary()
+    }
+
+    pub fn fonts(&self) -> &FontCollection<'static> {
+        &self.state.fonts
+    }
+
+    pub fn font_scale(&self) -> Scale {
+        self.state.font_scale
+    }
+
+    pub fn set_font(&mut self, font: Font<'static>, scale: Scale) {
+        self.set_fonts(FontCollection::new().with_font(font), scale);
+    }
+
+    pub fn set_fonts(&mut self, fonts: FontCollection<'static>, scale: Scale) {
+        self.state.fonts = fonts;
+        self.state.font_scale = scale;
+    }
+
+    pub fn text_anchor(&self) -> TextAnchor {
+        self.state.text_anchor
+    }
+
+    pub fn set_text_anchor(&mut self, anchor: TextAnchor) {
+        self.state.text_anchor = anchor;
+    }
+
+    pub fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode, mask: Option<Mask>) {
+        self.canvas.push_layer(opacity, blend_mode, mask);
+    }
+
+    pub fn pop_layer(&mut self) {
+        self.canvas.pop_layer();
+    }
+
+    pub fn fill_path(&mut self, path: &Path, fill_rule: FillRule) {
+        self.draw(|canvas, state| {
+            fill_path_transformed_mut(
+                canvas,
+                path,
+                &state.transform,
+                fill_rule,
+                GlobalAlpha(&*state.fill_paint, state.global_alpha),
+            );
+        });
+    }
+
+    pub fn stroke_path(&mut self, path: &Path) {
+        self.draw(|canvas, state| {
+            stroke_path_transformed_mut(
+                canvas,
+                path,
+                &state.transform,
+                &state.stroke_style,
+                GlobalAlpha(&*state.stroke_paint, state.global_alpha),
+            );
+        });
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let mut path = Path::new();
+        path.add_rect(x, y, width, height);
+        self.fill_path(&path, FillRule::NonZero);
+    }
+
+    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let mut path = Path::new();
+        path.add_rect(x, y, width, height);
+        self.stroke_path(&path);
+    }
+
+    pub fn fill_text(&mut self, text: &str, x: f32, y: f32) {
+        self.draw(|canvas, state| {
+            if let Some(outline) = state.text_outline(text, x, y) {
+                fill_path_transformed_mut(
+                    canvas,
+                    &outline,
+                    &state.transform,
+                    FillRule::NonZero,
+                    GlobalAlpha(&*state.fill_paint, state.global_alpha),
+                );
+            }
+        });
+    }
+
+    pub fn stroke_text(&mut self, text: &str, x: f32, y: f32) {
+        self.draw(|canvas, state| {
+            if let Some(outline) = state.text_outline(text, x, y) {
+                stroke_path_transformed_mut(
+                    canvas,
+                    &outline,
+                    &state.transform,
+                    &state.stroke_style,
+                    GlobalAlpha(&*state.stroke_paint, state.global_alpha),
+                );
+            }
+        });
+    }
+
+    fn canvas_bounds(&self) -> Option<Rect> {
+        let (width, height) = self.canvas.dimensions();
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(Rect::at(0, 0).of_size(width, height))
+    }
+
+    fn draw<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Composite<'_, Clipped<'_, LayerStack<C>>>, &GraphicsState<C::Pixel>),
+    {
+        let state = &self.state;
+        let bounds = match self.canvas_bounds() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let unclipped = Clip::from_rect(bounds, bounds);
+        let clip = state.clip.as_deref().unwrap_or(&unclipped);
+        let mut clipped = Clipped::new(&mut self.canvas, clip);
+        f(&mut Composite::new(&mut clipped, state.blend_mode), state);
+    }
+}
+
+struct GlobalAlpha<'a, P>(&'a dyn Paint<P>, f32);
+
+impl<P> Paint<P> for GlobalAlpha<'_, P> {
+    fn color_at(&self, x: f32, y: f32) -> P {
+        self.0.color_at(x, y)
+    }
+
+    fn coverage_at(&self, x: f32, y: f32) -> f32 {
+        self.0.coverage_at(x, y) * self.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    #[test]
+    fn drawing_on_empty_canvas_is_a_no_op() {
+        let mut context = Context::new(RgbImage::new(0, 0));
+        context.fill_rect(0.0, 0.0, 10.0, 10.0);
+        context.stroke_rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(context.into_inner().dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn huge_clip_is_limited_to_the_canvas() {
+        let mut context = Context::new(RgbImage::new(8, 8));
+        context.clip_rect(0.0, 0.0, 200000.0, 200000.0);
+        assert_eq!(
+            context.clip().and_then(|clip| clip.bounds()),
+            Some(Rect::at(0, 0).of_size(8, 8))
+        );
+        context.set_fill_paint(Rgb([255u8, 255, 255]));
+        context.fill_rect(0.0, 0.0, 8.0, 8.0);
+        assert!(context
+            .into_inner()
+            .pixels()
+            .all(|p| *p == Rgb([255, 255, 255])));
+    }
+
+    #[test]
+    fn offscreen_clip_discards_drawing() {
+        let mut context = Context::new(RgbImage::new(8, 8));
+        context.clip_rect(-5000.0, 100.0, 10000.0, 10000.0);
+        assert!(context.clip().unwrap().is_empty());
+        context.set_fill_paint(Rgb([255u8, 255, 255]));
+        context.fill_rect(0.0, 0.0, 8.0, 8.0);
+        assert!(context.into_inner().pixels().all(|p| *p == Rgb([0, 0, 0])));
+    }
+
+    #[test]
+    fn path_clip_attenuates_edges() {
+        let mut context = Context::new(RgbImage::new(4, 1));
+        context.clip_rect(0.0, 0.0, 2.5, 1.0);
+        context.set_fill_paint(Rgb([255u8, 255, 255]));
+        context.fill_rect(0.0, 0.0, 4.0, 1.0);
+        let image = context.into_inner();
+        assert_eq!(image.get_pixel(1, 0)[0], 255);
+        assert_eq!(image.get_pixel(2, 0)[0], 128);
+        assert_eq!(image.get_pixel(3, 0)[0], 0);
+    }
+
+    #[test]
+    fn layers_accumulate_in_float() {
+        let mut context = Context::new(RgbImage::new(2, 2));
+        context.set_fill_paint(Rgb([255u8, 255, 255]));
+        context.set_global_alpha(0.01);
+        context.push_layer(1.0, BlendMode::SourceOver, None);
+        for _ in 0..20 {
+            context.fill_rect(0.0, 0.0, 2.0, 2.0);
+        }
+        context.pop_layer();
+        let expected = ((1.0 - 0.99f32.powi(20)) * 255.0).round() as u8;
+        assert_eq!(context.into_inner().get_pixel(0, 0)[0], expected);
+    }
+
+    #[test]
+    fn save_and_restore_state() {
+        let mut context = Context::new(RgbImage::from_pixel(4, 4, Rgb([255, 255, 255])));
+        context.save();
+        context.set_fill_paint(Rgb([255u8, 0, 0]));
+        context.translate(2.0, 0.0);
+        context.fill_rect(0.0, 0.0, 2.0, 4.0);
+        context.restore();
+        context.fill_rect(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(context.transform(), Transform2D::identity());
+        let image = context.into_inner();
+        assert_eq!(*image.get_pixel(0, 0), Rgb([0, 0, 0]));
+        assert_eq!(*image.get_pixel(1, 1), Rgb([255, 255, 255]));
+        assert_eq!(*image.get_pixel(2, 1), Rgb([255, 0, 0]));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::core::drawing::line::draw_line_segment_mut;
+use crate::core::drawing::path::stroke_path_mut;
 use crate::core::drawing::Canvas;
-use crate::core::entity::definitions::Image;
-use image::{GenericImage, ImageBuffer};
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::paint::Paint;
+use crate::core::path::{Path, StrokeStyle};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
 use std::f32;
-use std::i32;
 
 pub fn draw_cubic_bezier_curve<I>(
     image: &I,
@@ -73,3 +76,49 @@ pub fn draw_cubic_bezier_curve_mut<C>(
         t1 = t2;
     }
 }
+
+pub fn draw_stroked_cubic_bezier_curve<I, T>(
+    image: &I,
+    start: (f32, f32),
+    end: (f32, f32),
+    control_a: (f32, f32),
+    control_b: (f32, f32),
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_cubic_bezier_curve_mut(&mut out, start, end, control_a, control_b, style, paint);
+    out
+}
+
+pub fn draw_stroked_cubic_bezier_curve_mut<C, T>(
+    canvas: &mut C,
+    start: (f32, f32),
+    end: (f32, f32),
+    control_a: (f32, f32),
+    control_b: (f32, f32),
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.move_to(start.0, start.1).cubic_to(
+        control_a.0,
+        control_a.1,
+        control_b.0,
+        control_b.1,
+        end.0,
+        end.1,
+    );
+    stroke_path_mut(canvas, &path, style, paint);
+}
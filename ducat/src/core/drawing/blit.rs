@@ -0,0 +1,177 @@
+use crate::core::drawing::{Canvas, FillRule, Rasterizer};
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+use crate::core::entity::transform::Transform2D;
+use crate::core::operation::math::cast;
+use crate::core::operation::premultiply::channel_max;
+use crate::core::operation::sample::{sample, Interpolation, Wrap};
+use crate::core::path::Path;
+use conv::ValueInto;
+use image::{GenericImage, GenericImageView, ImageBuffer, Pixel};
+
+pub fn draw_image<I, S>(
+    image: &I,
+    src: &S,
+    transform: &Transform2D,
+    interpolation: Interpolation,
+    opacity: f32,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    S: GenericImageView<Pixel = I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_image_mut(&mut out, src, transform, interpolation, opacity);
+    out
+}
+
+pub fn draw_image_mut<C, S>(
+    canvas: &mut C,
+    src: &S,
+    transform: &Transform2D,
+    interpolation: Interpolation,
+    opacity: f32,
+) where
+    C: Canvas,
+    S: GenericImageView<Pixel = C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = src.dimensions();
+    let opacity = opacity.clamp(0.0, 1.0);
+    let inverse = match transform.invert() {
+        Some(inverse) => inverse,
+        None => return,
+    };
+    if width == 0 || height == 0 || opacity <= 0.0 {
+        return;
+    }
+
+    let mut outline = Path::new();
+    outline.add_rect(0.0, 0.0, width as f32, height as f32);
+    let outline = outline.transform(transform);
+
+    let (canvas_width, canvas_height) = canvas.dimensions();
+    if canvas_width == 0 || canvas_height == 0 {
+        return;
+    }
+    let canvas_bounds = Rect::at(0, 0).of_size(canvas_width, canvas_height);
+    let region = match outline
+        .bounds()
+        .and_then(|bounds| bounds.to_rect_within(canvas_bounds))
+    {
+        Some(region) => region,
+        None => return,
+    };
+
+    let mut rasterizer = Rasterizer::new(region);
+    rasterizer.add_path(&outline);
+    rasterizer.for_each_pixel(FillRule::NonZero, |x, y, coverage| {
+        let p = inverse.apply(Point::new(x as f32 + 0.5, y as f32 + 0.5));
+        let color = sample(src, p.x, p.y, interpolation, Wrap::Clamp, Wrap::Clamp);
+        let (color, alpha) = split_alpha(color);
+        let coverage = coverage * alpha * opacity;
+        if coverage > 0.0 {
+            canvas.blend_pixel(x as u32, y as u32, color, coverage);
+        }
+    });
+}
+
+pub fn draw_image_rect<I, S>(
+    image: &I,
+    src: &S,
+    rect: Rect,
+    interpolation: Interpolation,
+    opacity: f32,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    S: GenericImageView<Pixel = I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_image_rect_mut(&mut out, src, rect, interpolation, opacity);
+    out
+}
+
+pub fn draw_image_rect_mut<C, S>(
+    canvas: &mut C,
+    src: &S,
+    rect: Rect,
+    interpolation: Interpolation,
+    opacity: f32,
+) where
+    C: Canvas,
+    S: GenericImageView<Pixel = C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = src.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let transform = Transform2D::scale(
+        rect.width() as f32 / width as f32,
+        rect.height() as f32 / height as f32,
+    )
+    .then(&Transform2D::translate(
+        rect.left() as f32,
+        rect.top() as f32,
+    ));
+    draw_image_mut(canvas, src, &transform, interpolation, opacity);
+}
+
+fn split_alpha<P: Pixel>(mut color: P) -> (P, f32)
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    if !P::COLOR_TYPE.has_alpha() {
+        return (color, 1.0);
+    }
+    let max = channel_max::<P::Subpixel>();
+    let channels = color.channels_mut();
+    let last = channels.len() - 1;
+    let alpha = cast(channels[last]) / max;
+    channels[last] = Clamp::clamp(max);
+    (color, alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn empty_canvas_is_ignored() {
+        let src = GrayImage::from_pixel(2, 2, Luma([255]));
+        let mut canvas = GrayImage::new(0, 0);
+        draw_image_mut(
+            &mut canvas,
+            &src,
+            &Transform2D::identity(),
+            Interpolation::Nearest,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn translated_copy_is_exact() {
+        let src = GrayImage::from_fn(2, 2, |x, y| Luma([(1 + x + 2 * y) as u8 * 50]));
+        let mut canvas = GrayImage::new(4, 4);
+        draw_image_mut(
+            &mut canvas,
+            &src,
+            &Transform2D::translate(1.0, 1.0),
+            Interpolation::Bilinear,
+            1.0,
+        );
+        assert_eq!(canvas.get_pixel(1, 1)[0], 50);
+        assert_eq!(canvas.get_pixel(2, 2)[0], 200);
+        assert_eq!(canvas.get_pixel(0, 0)[0], 0);
+        assert_eq!(canvas.get_pixel(3, 3)[0], 0);
+    }
+}
@@ -1,3 +1,8 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::operation::composite::{composite, BlendMode};
+use crate::core::operation::pixelops::weighted_sum;
+use crate::core::operation::srgb::{linear_weighted_sum, SrgbSubpixel};
+use conv::ValueInto;
 use image::{GenericImage, GenericImageView, Pixel};
 
 pub trait Canvas {
@@ -16,6 +21,35 @@ pub trait Canvas {
     fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel;
 
     fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel);
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32)
+    where
+        <Self::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    {
+        if coverage >= 1.0 {
+            self.draw_pixel(x, y, color);
+        } else if Self::Pixel::COLOR_TYPE.has_alpha() {
+            let pixel = self.get_pixel(x, y);
+            self.draw_pixel(x, y, composite(color, pixel, BlendMode::Source, coverage));
+        } else {
+            let pixel = self.get_pixel(x, y);
+            self.draw_pixel(x, y, weighted_sum(pixel, color, 1.0 - coverage, coverage));
+        }
+    }
+
+    fn composite_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        color: Self::Pixel,
+        mode: BlendMode,
+        coverage: f32,
+    ) where
+        <Self::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    {
+        let pixel = self.get_pixel(x, y);
+        self.draw_pixel(x, y, composite(color, pixel, mode, coverage));
+    }
 }
 
 impl<I> Canvas for I
@@ -53,4 +87,141 @@ impl<I: GenericImage> Canvas for Blend<I> {
     fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
         self.0.get_pixel_mut(x, y).blend(&color)
     }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32)
+    where
+        <Self::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    {
+        let pixel = self.0.get_pixel(x, y);
+        self.0.put_pixel(
+            x,
+            y,
+            composite(color, pixel, BlendMode::SourceOver, coverage),
+        );
+    }
+}
+
+pub struct LinearLight<'a, C> {
+    canvas: &'a mut C,
+}
+
+impl<'a, C: Canvas> LinearLight<'a, C> {
+    pub fn new(canvas: &'a mut C) -> LinearLight<'a, C> {
+        LinearLight { canvas }
+    }
+}
+
+impl<C> Canvas for LinearLight<'_, C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: SrgbSubpixel,
+{
+    type Pixel = C::Pixel;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+        self.canvas.get_pixel(x, y)
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
+        self.canvas.draw_pixel(x, y, color);
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32)
+    where
+        <Self::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    {
+        if coverage >= 1.0 {
+            self.canvas.draw_pixel(x, y, color);
+        } else {
+            let pixel = self.canvas.get_pixel(x, y);
+            let blended = linear_weighted_sum(pixel, color, 1.0 - coverage, coverage);
+            self.canvas.draw_pixel(x, y, blended);
+        }
+    }
+}
+
+pub struct Composite<'a, C> {
+    canvas: &'a mut C,
+    mode: BlendMode,
+}
+
+impl<'a, C: Canvas> Composite<'a, C> {
+    pub fn new(canvas: &'a mut C, mode: BlendMode) -> Composite<'a, C> {
+        Composite { canvas, mode }
+    }
+
+    pub fn mode(&self) -> BlendMode {
+        self.mode
+    }
+}
+
+impl<C> Canvas for Composite<'_, C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    type Pixel = C::Pixel;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+        self.canvas.get_pixel(x, y)
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
+        self.blend_pixel(x, y, color, 1.0);
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32) {
+        self.canvas
+            .composite_pixel(x, y, color, self.mode, coverage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::drawing::{
+        draw_antialiased_line_segment_linear_mut, draw_antialiased_line_segment_mut,
+    };
+    use crate::core::operation::pixelops::interpolate;
+    use image::{GrayImage, Luma, Rgba, RgbaImage};
+
+    #[test]
+    fn linear_light_blends_coverage_in_linear_space() {
+        let mut image = GrayImage::new(1, 1);
+        Canvas::blend_pixel(&mut image, 0, 0, Luma([255]), 0.5);
+        assert_eq!(image.get_pixel(0, 0)[0], 127);
+
+        let mut image = GrayImage::new(1, 1);
+        LinearLight::new(&mut image).blend_pixel(0, 0, Luma([255]), 0.5);
+        assert_eq!(image.get_pixel(0, 0)[0], 188);
+    }
+
+    #[test]
+    fn linear_light_matches_default_canvas_at_full_coverage() {
+        let mut image = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 255, 255]));
+        LinearLight::new(&mut image).blend_pixel(0, 0, Rgba([255, 0, 0, 128]), 1.0);
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 128]));
+
+        let mut image = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 255, 255]));
+        LinearLight::new(&mut image).draw_pixel(0, 0, Rgba([255, 0, 0, 128]));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 128]));
+    }
+
+    #[test]
+    fn linear_antialiased_line_is_lighter_at_edges() {
+        let mut srgb = GrayImage::new(5, 3);
+        draw_antialiased_line_segment_mut(&mut srgb, (0, 0), (4, 2), Luma([255]), interpolate);
+        let mut linear = GrayImage::new(5, 3);
+        draw_antialiased_line_segment_linear_mut(&mut linear, (0, 0), (4, 2), Luma([255]));
+        assert_eq!(srgb.get_pixel(1, 0)[0], 127);
+        assert_eq!(linear.get_pixel(1, 0)[0], 188);
+    }
 }
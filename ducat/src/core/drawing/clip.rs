@@ -0,0 +1,183 @@
+use crate::core::drawing::{Canvas, FillRule, Rasterizer};
+use crate::core::entity::definitions::Clamp;
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::{Rect, Region};
+use crate::core::operation::composite::BlendMode;
+use crate::core::path::Path;
+use conv::ValueInto;
+use image::Pixel;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clip {
+    bounds: Option<Rect>,
+    coverage: Option<Vec<f32>>,
+}
+
+impl Clip {
+    pub fn from_rect(rect: Rect, canvas_bounds: Rect) -> Clip {
+        Clip {
+            bounds: rect.intersect(canvas_bounds),
+            coverage: None,
+        }
+    }
+
+    pub fn from_path(path: &Path, fill_rule: FillRule, canvas_bounds: Rect) -> Clip {
+        let region = match path
+            .bounds()
+            .and_then(|bounds| bounds.to_rect_within(canvas_bounds))
+        {
+            Some(region) => region,
+            None => return Clip::empty(),
+        };
+
+        let mut rasterizer = Rasterizer::new(region);
+        rasterizer.add_path(path);
+        let mut coverage = vec![0.0; area(region)];
+        rasterizer.for_each_pixel(fill_rule, |x, y, c| {
+            coverage[index(region, x, y)] = c;
+        });
+
+        if coverage.iter().all(|&c| c >= 1.0) {
+            Clip::from_rect(region, canvas_bounds)
+        } else {
+            Clip {
+                bounds: Some(region),
+                coverage: Some(coverage),
+            }
+        }
+    }
+
+    pub fn from_polygon(points: &[Point<f32>], fill_rule: FillRule, canvas_bounds: Rect) -> Clip {
+        let mut path = Path::new();
+        path.add_polygon(points);
+        Clip::from_path(&path, fill_rule, canvas_bounds)
+    }
+
+    pub fn empty() -> Clip {
+        Clip {
+            bounds: None,
+            coverage: None,
+        }
+    }
+
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_none()
+    }
+
+    pub fn coverage(&self, x: i32, y: i32) -> f32 {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => return 0.0,
+        };
+        if !bounds.contains(x, y) {
+            return 0.0;
+        }
+        match &self.coverage {
+            Some(coverage) => coverage[index(bounds, x, y)],
+            None => 1.0,
+        }
+    }
+
+    pub fn intersect(&self, other: &Clip) -> Clip {
+        let bounds = match (self.bounds, other.bounds) {
+            (Some(a), Some(b)) => match a.intersect(b) {
+                Some(bounds) => bounds,
+                None => return Clip::empty(),
+            },
+            _ => return Clip::empty(),
+        };
+
+        if self.coverage.is_none() && other.coverage.is_none() {
+            return Clip {
+                bounds: Some(bounds),
+                coverage: None,
+            };
+        }
+
+        let mut coverage = Vec::with_capacity(area(bounds));
+        for y in bounds.top()..=bounds.bottom() {
+            for x in bounds.left()..=bounds.right() {
+                coverage.push(self.coverage(x, y) * other.coverage(x, y));
+            }
+        }
+        Clip {
+            bounds: Some(bounds),
+            coverage: Some(coverage),
+        }
+    }
+}
+
+fn area(rect: Rect) -> usize {
+    (rect.width() as usize)
+        .checked_mul(rect.height() as usize)
+        .expect("clip region is too large")
+}
+
+fn index(rect: Rect, x: i32, y: i32) -> usize {
+    (y - rect.top()) as usize * rect.width() as usize + (x - rect.left()) as usize
+}
+
+pub struct Clipped<'a, C> {
+    canvas: &'a mut C,
+    clip: &'a Clip,
+}
+
+impl<'a, C: Canvas> Clipped<'a, C> {
+    pub fn new(canvas: &'a mut C, clip: &'a Clip) -> Clipped<'a, C> {
+        Clipped { canvas, clip }
+    }
+
+    pub fn clip(&self) -> &Clip {
+        self.clip
+    }
+}
+
+impl<C> Canvas for Clipped<'_, C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    type Pixel = C::Pixel;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+        self.canvas.get_pixel(x, y)
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
+        let coverage = self.clip.coverage(x as i32, y as i32);
+        if coverage >= 1.0 {
+            self.canvas.draw_pixel(x, y, color);
+        } else if coverage > 0.0 {
+            self.canvas.blend_pixel(x, y, color, coverage);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32) {
+        let coverage = coverage * self.clip.coverage(x as i32, y as i32);
+        if coverage > 0.0 {
+            self.canvas.blend_pixel(x, y, color, coverage);
+        }
+    }
+
+    fn composite_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        color: Self::Pixel,
+        mode: BlendMode,
+        coverage: f32,
+    ) {
+        let coverage = coverage * self.clip.coverage(x as i32, y as i32);
+        if coverage > 0.0 {
+            self.canvas.composite_pixel(x, y, color, mode, coverage);
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use crate::core::drawing::draw_if_in_bounds;
 use crate::core::drawing::line::draw_line_segment_mut;
-use crate::core::drawing::Canvas;
-use crate::core::entity::definitions::Image;
-use image::{GenericImage, ImageBuffer};
+use crate::core::drawing::path::{fill_path_mut, stroke_path_mut};
+use crate::core::drawing::{Canvas, FillRule};
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::paint::Paint;
+use crate::core::path::{Path, StrokeStyle};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
 use std::f32;
-use std::i32;
 
 pub fn draw_hollow_ellipse<I>(
     image: &I,
@@ -252,3 +255,139 @@ where
     draw_filled_circle_mut(&mut out, center, radius, color);
     out
 }
+
+pub fn draw_antialiased_filled_ellipse<I, T>(
+    image: &I,
+    center: (f32, f32),
+    width_radius: f32,
+    height_radius: f32,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_antialiased_filled_ellipse_mut(&mut out, center, width_radius, height_radius, paint);
+    out
+}
+
+pub fn draw_antialiased_filled_ellipse_mut<C, T>(
+    canvas: &mut C,
+    center: (f32, f32),
+    width_radius: f32,
+    height_radius: f32,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_ellipse(center.0, center.1, width_radius, height_radius);
+    fill_path_mut(canvas, &path, FillRule::NonZero, paint);
+}
+
+pub fn draw_antialiased_filled_circle<I, T>(
+    image: &I,
+    center: (f32, f32),
+    radius: f32,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_antialiased_filled_circle_mut(&mut out, center, radius, paint);
+    out
+}
+
+pub fn draw_antialiased_filled_circle_mut<C, T>(
+    canvas: &mut C,
+    center: (f32, f32),
+    radius: f32,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    draw_antialiased_filled_ellipse_mut(canvas, center, radius, radius, paint);
+}
+
+pub fn draw_stroked_ellipse<I, T>(
+    image: &I,
+    center: (f32, f32),
+    width_radius: f32,
+    height_radius: f32,
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_ellipse_mut(&mut out, center, width_radius, height_radius, style, paint);
+    out
+}
+
+pub fn draw_stroked_ellipse_mut<C, T>(
+    canvas: &mut C,
+    center: (f32, f32),
+    width_radius: f32,
+    height_radius: f32,
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_ellipse(center.0, center.1, width_radius, height_radius);
+    stroke_path_mut(canvas, &path, style, paint);
+}
+
+pub fn draw_stroked_circle<I, T>(
+    image: &I,
+    center: (f32, f32),
+    radius: f32,
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_circle_mut(&mut out, center, radius, style, paint);
+    out
+}
+
+pub fn draw_stroked_circle_mut<C, T>(
+    canvas: &mut C,
+    center: (f32, f32),
+    radius: f32,
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    draw_stroked_ellipse_mut(canvas, center, radius, radius, style, paint);
+}
@@ -1,7 +1,6 @@
 use crate::core::drawing::Canvas;
 use crate::core::entity::definitions::Image;
 use image::{GenericImage, ImageBuffer};
-use std::i32;
 
 #[rustfmt::skip]
 pub fn draw_cross_mut<C>(canvas: &mut C, color: C::Pixel, x: i32, y: i32)
@@ -0,0 +1,382 @@
+use crate::core::drawing::Canvas;
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::entity::point::Point;
+use crate::core::paint::Paint;
+use crate::core::text::{shape, FontSource};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
+use rusttype::{point, Font, PositionedGlyph, Scale};
+use std::collections::{BTreeMap, HashMap};
+use std::mem::size_of;
+use std::sync::Arc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font: usize,
+    glyph: u16,
+    scale: (u32, u32),
+    offset: (u32, u32),
+}
+
+struct CachedGlyph {
+    left: i32,
+    top: i32,
+    width: u32,
+    coverage: Vec<u8>,
+    last_used: u64,
+}
+
+impl CachedGlyph {
+    fn size(&self) -> usize {
+        self.coverage.len() + size_of::<CachedGlyph>() + size_of::<GlyphKey>()
+    }
+}
+
+struct CachedFont<'font> {
+    _font: Font<'font>,
+    glyphs: usize,
+}
+
+const CACHED_FONT_SIZE: usize = size_of::<CachedFont>() + size_of::<usize>();
+
+pub struct GlyphCache<'font> {
+    budget: usize,
+    usage: usize,
+    subpixel_steps: u32,
+    clock: u64,
+    glyphs: HashMap<GlyphKey, CachedGlyph>,
+    recency: BTreeMap<u64, GlyphKey>,
+    fonts: HashMap<usize, CachedFont<'font>>,
+}
+
+impl<'font> GlyphCache<'font> {
+    pub fn new(budget: usize) -> GlyphCache<'font> {
+        GlyphCache {
+            budget,
+            usage: 0,
+            subpixel_steps: 4,
+            clock: 0,
+            glyphs: HashMap::new(),
+            recency: BTreeMap::new(),
+            fonts: HashMap::new(),
+        }
+    }
+
+    pub fn with_subpixel_steps(mut self, steps: u32) -> GlyphCache<'font> {
+        self.subpixel_steps = steps.max(1);
+        self.clear();
+        self
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict_until(budget);
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.usage
+    }
+
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+        self.recency.clear();
+        self.fonts.clear();
+        self.usage = 0;
+    }
+
+    pub fn draw_glyph_mut<C, T>(
+        &mut self,
+        canvas: &mut C,
+        paint: &T,
+        glyph: &PositionedGlyph<'font>,
+        x: i32,
+        y: i32,
+    ) where
+        C: Canvas,
+        T: Paint<C::Pixel>,
+        <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    {
+        let steps = self.subpixel_steps as f32;
+        let position = glyph.position();
+        let (ox, fx) = split_position(position.x, steps);
+        let (oy, fy) = split_position(position.y, steps);
+        let key = GlyphKey {
+            font: font_key(glyph.font()),
+            glyph: glyph.id().0,
+            scale: (glyph.scale().x.to_bits(), glyph.scale().y.to_bits()),
+            offset: (fx, fy),
+        };
+
+        self.clock += 1;
+        let clock = self.clock;
+        let cached = match self.glyphs.get_mut(&key) {
+            Some(cached) => {
+                self.recency.remove(&cached.last_used);
+                cached.last_used = clock;
+                self.recency.insert(clock, key);
+                &*cached
+            }
+            None => {
+                let offset = point(fx as f32 / steps, fy as f32 / steps);
+                let rasterized = rasterize(glyph, offset, clock);
+                let size = rasterized.size();
+                if size + CACHED_FONT_SIZE > self.budget {
+                    blit(canvas, paint, &rasterized, x + ox, y + oy);
+                    return;
+                }
+                self.evict_until(self.budget - size - CACHED_FONT_SIZE);
+                self.retain_font(key.font, glyph.font());
+                self.usage += size;
+                self.recency.insert(clock, key);
+                &*self.glyphs.entry(key).or_insert(rasterized)
+            }
+        };
+        blit(canvas, paint, cached, x + ox, y + oy);
+    }
+
+    fn retain_font(&mut self, key: usize, font: &Font<'font>) {
+        let usage = &mut self.usage;
+        let cached = self.fonts.entry(key).or_insert_with(|| {
+            *usage += CACHED_FONT_SIZE;
+            CachedFont {
+                _font: font.clone(),
+                glyphs: 0,
+            }
+        });
+        cached.glyphs += 1;
+    }
+
+    fn release_font(&mut self, key: usize) {
+        if let Some(cached) = self.fonts.get_mut(&key) {
+            cached.glyphs -= 1;
+            if cached.glyphs == 0 {
+                self.fonts.remove(&key);
+                self.usage -= CACHED_FONT_SIZE;
+            }
+        }
+    }
+
+    fn evict_until(&mut self, limit: usize) {
+        while self.usage > limit {
+            let key = match self.recency.pop_first() {
+                Some((_, key)) => key,
+                None => break,
+            };
+            if let Some(evicted) = self.glyphs.remove(&key) {
+                self.usage -= evicted.size();
+                self.release_font(key.font);
+            }
+        }
+    }
+}
+
+fn font_key(font: &Font) -> usize {
+    match font {
+        Font::Ref(face) => Arc::as_ptr(face) as *const () as usize,
+        Font::Owned(face) => Arc::as_ptr(face) as *const () as usize,
+    }
+}
+
+fn split_position(v: f32, steps: f32) -> (i32, u32) {
+    let whole = v.floor();
+    let bucket = ((v - whole) * steps).round();
+    if bucket >= steps {
+        (whole as i32 + 1, 0)
+    } else {
+        (whole as i32, bucket as u32)
+    }
+}
+
+fn rasterize(glyph: &PositionedGlyph, offset: rusttype::Point<f32>, clock: u64) -> CachedGlyph {
+    let glyph = glyph.unpositioned().clone().positioned(offset);
+    let bb = match glyph.pixel_bounding_box() {
+        Some(bb) => bb,
+        None => {
+            return CachedGlyph {
+                left: 0,
+                top: 0,
+                width: 0,
+                coverage: Vec::new(),
+                last_used: clock,
+            }
+        }
+    };
+    let width = bb.width() as u32;
+    let mut coverage = vec![0; (bb.width() * bb.height()) as usize];
+    glyph.draw(|gx, gy, v| {
+        coverage[(gy * width + gx) as usize] = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    });
+    CachedGlyph {
+        left: bb.min.x,
+        top: bb.min.y,
+        width,
+        coverage,
+        last_used: clock,
+    }
+}
+
+fn blit<C, T>(canvas: &mut C, paint: &T, glyph: &CachedGlyph, x: i32, y: i32)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    if glyph.width == 0 {
+        return;
+    }
+    let (image_width, image_height) = (canvas.width() as i32, canvas.height() as i32);
+    for (i, &v) in glyph.coverage.iter().enumerate() {
+        if v == 0 {
+            continue;
+        }
+        let image_x = x + glyph.left + (i as u32 % glyph.width) as i32;
+        let image_y = y + glyph.top + (i as u32 / glyph.width) as i32;
+        if (0..image_width).contains(&image_x) && (0..image_height).contains(&image_y) {
+            let (cx, cy) = (image_x as f32 + 0.5, image_y as f32 + 0.5);
+            let coverage = v as f32 / 255.0 * paint.coverage_at(cx, cy);
+            if coverage > 0.0 {
+                canvas.blend_pixel(
+                    image_x as u32,
+                    image_y as u32,
+                    paint.color_at(cx, cy),
+                    coverage,
+                );
+            }
+        }
+    }
+}
+
+pub fn draw_text_cached_mut<'font, C, T, F>(
+    canvas: &mut C,
+    cache: &mut GlyphCache<'font>,
+    paint: T,
+    position: Point<f32>,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) where
+    C: Canvas,
+    F: FontSource<'font>,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let ascent = match font.primary() {
+        Some(primary) => primary.v_metrics(scale).ascent,
+        None => return,
+    };
+    for shaped in shape(font, scale, text) {
+        let glyph = shaped
+            .glyph
+            .positioned(point(position.x + shaped.x, position.y + ascent));
+        cache.draw_glyph_mut(canvas, &paint, &glyph, 0, 0);
+    }
+}
+
+pub fn draw_text_cached<'font, I, T, F>(
+    image: &I,
+    cache: &mut GlyphCache<'font>,
+    paint: T,
+    position: Point<f32>,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    F: FontSource<'font>,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_cached_mut(&mut out, cache, paint, position, scale, font, text);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::drawing::draw_text_mut;
+    use crate::core::paint::Solid;
+    use crate::core::text::test_font;
+    use image::{GrayImage, Luma};
+
+    fn draw<'font>(cache: &mut GlyphCache<'font>, font: &Font<'font>, text: &str) -> GrayImage {
+        let mut image = GrayImage::new(260, 40);
+        let paint = Solid(Luma([255u8]));
+        let position = Point::new(10.0, 10.0);
+        let scale = Scale::uniform(17.3);
+        draw_text_cached_mut(&mut image, cache, paint, position, scale, font, text);
+        image
+    }
+
+    #[test]
+    fn matches_direct_drawing_within_subpixel_bucket() {
+        let font = test_font();
+        let text = "Hamburgefonstiv Wavy 0123";
+        let mut direct = GrayImage::new(260, 40);
+        draw_text_mut(
+            &mut direct,
+            Luma([255]),
+            10,
+            10,
+            Scale::uniform(17.3),
+            &font,
+            text,
+        );
+        for &steps in &[4, 64] {
+            let mut cache = GlyphCache::new(1 << 20).with_subpixel_steps(steps);
+            let cached = draw(&mut cache, &font, text);
+            let tolerance = (255 + steps as i32 - 1) / steps as i32 + 2;
+            for (a, b) in direct.pixels().zip(cached.pixels()) {
+                assert!((a[0] as i32 - b[0] as i32).abs() <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_text_hits_the_cache() {
+        let font = test_font();
+        let mut cache = GlyphCache::new(1 << 20);
+        let first = draw(&mut cache, &font, "abab");
+        let (len, usage) = (cache.len(), cache.memory_usage());
+        assert_eq!(draw(&mut cache, &font, "abab"), first);
+        assert_eq!((cache.len(), cache.memory_usage()), (len, usage));
+    }
+
+    #[test]
+    fn fonts_are_budgeted_and_evicted_with_their_glyphs() {
+        let (first, second) = (test_font(), test_font());
+        let mut cache = GlyphCache::new(1 << 20);
+        draw(&mut cache, &first, "abc");
+        let glyphs: usize = cache.glyphs.values().map(CachedGlyph::size).sum();
+        assert_eq!(cache.memory_usage(), glyphs + CACHED_FONT_SIZE);
+        let first_usage = cache.memory_usage();
+
+        draw(&mut cache, &second, "abc");
+        assert_eq!(cache.fonts.len(), 2);
+
+        cache.set_budget(cache.memory_usage() - first_usage);
+        assert_eq!(cache.fonts.len(), 1);
+        assert!(cache.fonts.contains_key(&font_key(&second)));
+        assert_eq!(cache.memory_usage(), first_usage);
+
+        cache.set_budget(0);
+        assert!(cache.is_empty());
+        assert!(cache.fonts.is_empty());
+        assert_eq!(cache.memory_usage(), 0);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::core::drawing::{Canvas, Mask, PremultipliedImage};
+use crate::core::entity::definitions::Clamp;
+use crate::core::entity::rect::Rect;
+use crate::core::operation::composite::{composite_premultiplied, BlendMode};
+use crate::core::operation::premultiply::{premultiply, unpremultiply};
+use conv::ValueInto;
+use image::Pixel;
+
+struct Layer<P> {
+    buffer: PremultipliedImage<P>,
+    opacity: f32,
+    blend_mode: BlendMode,
+    mask: Option<Mask>,
+    dirty: Option<Rect>,
+}
+
+impl<P> Layer<P> {
+    fn mark_dirty(&mut self, region: Rect) {
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => {
+                let left = dirty.left().min(region.left());
+                let top = dirty.top().min(region.top());
+                let right = dirty.right().max(region.right());
+                let bottom = dirty.bottom().max(region.bottom());
+                Rect::at(left, top).of_size((right - left + 1) as u32, (bottom - top + 1) as u32)
+            }
+            None => region,
+        });
+    }
+}
+
+pub struct LayerStack<C: Canvas> {
+    canvas: C,
+    layers: Vec<Layer<C::Pixel>>,
+}
+
+impl<C> LayerStack<C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    pub fn new(canvas: C) -> LayerStack<C> {
+        LayerStack {
+            canvas,
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn canvas(&self) -> &C {
+        &self.canvas
+    }
+
+    pub fn canvas_mut(&mut self) -> &mut C {
+        &mut self.canvas
+    }
+
+    pub fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode, mask: Option<Mask>) {
+        let (width, height) = self.canvas.dimensions();
+        self.layers.push(Layer {
+            buffer: PremultipliedImage::new(width, height),
+            opacity: opacity.clamp(0.0, 1.0),
+            blend_mode,
+            mask,
+            dirty: None,
+        });
+    }
+
+    pub fn pop_layer(&mut self) {
+        let layer = match self.layers.pop() {
+            Some(layer) => layer,
+            None => return,
+        };
+
+        let region = if layer.blend_mode.keeps_uncovered_destination() {
+            layer.dirty
+        } else {
+            let (width, height) = layer.buffer.dimensions();
+            if width == 0 || height == 0 {
+                None
+            } else {
+                Some(Rect::at(0, 0).of_size(width, height))
+            }
+        };
+        let region = match region {
+            Some(region) => region,
+            None => return,
+        };
+        if let Some(below) = self.layers.last_mut() {
+            below.mark_dirty(region);
+        }
+
+        for y in region.top() as u32..=region.bottom() as u32 {
+            for x in region.left() as u32..=region.right() as u32 {
+                let coverage = match &layer.mask {
+                    Some(mask) => layer.opacity * mask.value(x as i32, y as i32),
+                    None => layer.opacity,
+                };
+                let src = layer.buffer.premultiplied_pixel(x, y);
+                match self.layers.last_mut() {
+                    Some(below) => {
+                        let dst = below.buffer.premultiplied_pixel(x, y);
+                        let out = composite_premultiplied(src, dst, layer.blend_mode, coverage);
+                        below.buffer.put_premultiplied_pixel(x, y, out);
+                    }
+                    None => {
+                        let dst = premultiply(&self.canvas.get_pixel(x, y));
+                        let out = composite_premultiplied(src, dst, layer.blend_mode, coverage);
+                        self.canvas.draw_pixel(x, y, unpremultiply(out));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn into_inner(mut self) -> C {
+        while !self.layers.is_empty() {
+            self.pop_layer();
+        }
+        self.canvas
+    }
+}
+
+impl<C> Canvas for LayerStack<C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    type Pixel = C::Pixel;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+        match self.layers.last() {
+            Some(layer) => layer.buffer.get_pixel(x, y),
+            None => self.canvas.get_pixel(x, y),
+        }
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
+        match self.layers.last_mut() {
+            Some(layer) => {
+                layer.mark_dirty(Rect::at(x as i32, y as i32).of_size(1, 1));
+                layer.buffer.draw_pixel(x, y, color);
+            }
+            None => self.canvas.draw_pixel(x, y, color),
+        }
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32) {
+        match self.layers.last_mut() {
+            Some(layer) => {
+                layer.mark_dirty(Rect::at(x as i32, y as i32).of_size(1, 1));
+                layer.buffer.blend_pixel(x, y, color, coverage);
+            }
+            None => self.canvas.blend_pixel(x, y, color, coverage),
+        }
+    }
+
+    fn composite_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        color: Self::Pixel,
+        mode: BlendMode,
+        coverage: f32,
+    ) {
+        match self.layers.last_mut() {
+            Some(layer) => {
+                layer.mark_dirty(Rect::at(x as i32, y as i32).of_size(1, 1));
+                layer.buffer.composite_pixel(x, y, color, mode, coverage);
+            }
+            None => self.canvas.composite_pixel(x, y, color, mode, coverage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    struct Counting {
+        image: RgbaImage,
+        writes: usize,
+    }
+
+    impl Canvas for Counting {
+        type Pixel = Rgba<u8>;
+
+        fn dimensions(&self) -> (u32, u32) {
+            self.image.dimensions()
+        }
+
+        fn get_pixel(&self, x: u32, y: u32) -> Rgba<u8> {
+            *self.image.get_pixel(x, y)
+        }
+
+        fn draw_pixel(&mut self, x: u32, y: u32, color: Rgba<u8>) {
+            self.writes += 1;
+            self.image.put_pixel(x, y, color);
+        }
+    }
+
+    fn black(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]))
+    }
+
+    #[test]
+    fn layer_writes_composite_source_over() {
+        let mut stack = LayerStack::new(black(1, 1));
+        stack.push_layer(1.0, BlendMode::SourceOver, None);
+        stack.blend_pixel(0, 0, Rgba([255, 255, 255, 128]), 1.0);
+        stack.blend_pixel(0, 0, Rgba([255, 255, 255, 128]), 1.0);
+        let image = stack.into_inner();
+        assert_eq!(*image.get_pixel(0, 0), Rgba([192, 192, 192, 255]));
+    }
+
+    #[test]
+    fn group_opacity_does_not_double_darken() {
+        let mut stack = LayerStack::new(black(1, 1));
+        stack.push_layer(0.5, BlendMode::SourceOver, None);
+        stack.blend_pixel(0, 0, Rgba([255, 255, 255, 255]), 1.0);
+        stack.blend_pixel(0, 0, Rgba([255, 255, 255, 255]), 1.0);
+        let image = stack.into_inner();
+        assert_eq!(*image.get_pixel(0, 0), Rgba([128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn pop_layer_only_touches_dirty_bounds() {
+        let mut stack = LayerStack::new(Counting {
+            image: black(16, 16),
+            writes: 0,
+        });
+        stack.push_layer(1.0, BlendMode::SourceOver, None);
+        stack.blend_pixel(2, 3, Rgba([255, 0, 0, 255]), 0.5);
+        stack.blend_pixel(4, 3, Rgba([255, 0, 0, 255]), 0.5);
+        stack.pop_layer();
+        assert_eq!(stack.canvas().writes, 3);
+        assert_eq!(*stack.canvas().image.get_pixel(3, 3), Rgba([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn unbounded_modes_composite_whole_canvas() {
+        let mut stack = LayerStack::new(black(4, 4));
+        stack.push_layer(1.0, BlendMode::SourceIn, None);
+        stack.draw_pixel(1, 1, Rgba([255, 255, 255, 255]));
+        let image = stack.into_inner();
+        assert_eq!(*image.get_pixel(1, 1), Rgba([255, 255, 255, 255]));
+        assert_eq!(*image.get_pixel(3, 3), Rgba([0, 0, 0, 0]));
+    }
+}
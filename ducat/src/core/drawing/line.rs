@@ -1,8 +1,12 @@
+use crate::core::drawing::path::stroke_path_mut;
 use crate::core::drawing::Canvas;
-use crate::core::entity::definitions::Image;
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::operation::srgb::{linear_interpolate, SrgbSubpixel};
+use crate::core::paint::Paint;
+use crate::core::path::{Path, StrokeStyle};
+use conv::ValueInto;
 use image::{GenericImage, ImageBuffer, Pixel};
 use std::f32;
-use std::i32;
 use std::mem::{swap, transmute};
 
 pub struct BresenhamLineIter {
@@ -133,7 +137,7 @@ impl<'a, P: Pixel + 'static> BresenhamLinePixelIterMut<'a, P> {
         );
         assert!(P::CHANNEL_COUNT > 0);
         assert!(
-            image.width() < i32::max_value() as u32 && image.height() < i32::max_value() as u32,
+            image.width() < i32::MAX as u32 && image.height() < i32::MAX as u32,
             "Image dimensions are too large"
         );
         let iter = BresenhamLineIter::new(clamp_point(start, image), clamp_point(end, image));
@@ -188,6 +192,41 @@ where
     }
 }
 
+pub fn draw_stroked_line_segment<I, T>(
+    image: &I,
+    start: (f32, f32),
+    end: (f32, f32),
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_line_segment_mut(&mut out, start, end, style, paint);
+    out
+}
+
+pub fn draw_stroked_line_segment_mut<C, T>(
+    canvas: &mut C,
+    start: (f32, f32),
+    end: (f32, f32),
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.move_to(start.0, start.1).line_to(end.0, end.1);
+    stroke_path_mut(canvas, &path, style, paint);
+}
+
 pub fn draw_antialiased_line_segment<I, B>(
     image: &I,
     start: (i32, i32),
@@ -247,6 +286,33 @@ pub fn draw_antialiased_line_segment_mut<I, B>(
     };
 }
 
+pub fn draw_antialiased_line_segment_linear<I>(
+    image: &I,
+    start: (i32, i32),
+    end: (i32, i32),
+    color: I::Pixel,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: SrgbSubpixel,
+{
+    draw_antialiased_line_segment(image, start, end, color, linear_interpolate)
+}
+
+pub fn draw_antialiased_line_segment_linear_mut<I>(
+    image: &mut I,
+    start: (i32, i32),
+    end: (i32, i32),
+    color: I::Pixel,
+) where
+    I: GenericImage,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: SrgbSubpixel,
+{
+    draw_antialiased_line_segment_mut(image, start, end, color, linear_interpolate);
+}
+
 fn plot_wu_line<I, T, B>(
     mut plotter: Plotter<'_, I, T, B>,
     start: (i32, i32),
@@ -0,0 +1,338 @@
+use crate::core::drawing::Canvas;
+use crate::core::entity::definitions::Clamp;
+use crate::core::operation::composite::BlendMode;
+use crate::core::operation::premultiply::unpack;
+use conv::ValueInto;
+use image::{GenericImageView, Luma, Pixel};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mask {
+    width: u32,
+    height: u32,
+    data: Vec<f32>,
+}
+
+impl Mask {
+    pub fn new(width: u32, height: u32) -> Mask {
+        Mask::filled(width, height, 0.0)
+    }
+
+    pub fn filled(width: u32, height: u32, value: f32) -> Mask {
+        Mask {
+            width,
+            height,
+            data: vec![value.clamp(0.0, 1.0); area(width, height)],
+        }
+    }
+
+    pub fn from_alpha<I>(image: &I) -> Mask
+    where
+        I: GenericImageView,
+        <I::Pixel as Pixel>::Subpixel: ValueInto<f32>,
+    {
+        Mask::from_fn(image, |_, alpha| alpha)
+    }
+
+    pub fn from_luminance<I>(image: &I) -> Mask
+    where
+        I: GenericImageView,
+        <I::Pixel as Pixel>::Subpixel: ValueInto<f32>,
+    {
+        Mask::from_fn(image, |color, alpha| {
+            (0.2125 * color[0] + 0.7154 * color[1] + 0.0721 * color[2]) * alpha
+        })
+    }
+
+    fn from_fn<I, F>(image: &I, f: F) -> Mask
+    where
+        I: GenericImageView,
+        <I::Pixel as Pixel>::Subpixel: ValueInto<f32>,
+        F: Fn([f32; 3], f32) -> f32,
+    {
+        let (width, height) = image.dimensions();
+        let mut data = Vec::with_capacity(area(width, height));
+        for y in 0..height {
+            for x in 0..width {
+                let (color, alpha) = unpack(&image.get_pixel(x, y));
+                data.push(f(color, alpha).clamp(0.0, 1.0));
+            }
+        }
+        Mask {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn value(&self, x: i32, y: i32) -> f32 {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return 0.0;
+        }
+        self.data[self.index(x as u32, y as u32)]
+    }
+
+    pub fn set_value(&mut self, x: u32, y: u32, value: f32) {
+        let i = self.index(x, y);
+        self.data[i] = value.clamp(0.0, 1.0);
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    pub fn invert(&mut self) {
+        self.data.iter_mut().for_each(|v| *v = 1.0 - *v);
+    }
+
+    pub fn blur(&mut self, radius: f32) {
+        if radius.is_nan() || radius <= 0.0 || self.data.is_empty() {
+            return;
+        }
+        let sigma = (radius / 2.0).min(self.width.max(self.height) as f32);
+        let half = (3.0 * sigma).ceil() as i32;
+        let mut kernel: Vec<f32> = (-half..=half)
+            .map(|i| (-(i as f32).powi(2) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f32 = kernel.iter().sum();
+        kernel.iter_mut().for_each(|k| *k /= total);
+
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut horizontal = vec![0.0; self.data.len()];
+        for y in 0..height {
+            for x in 0..width {
+                horizontal[self.index(x as u32, y as u32)] = kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(i, k)| k * self.value(x + i as i32 - half, y))
+                    .sum();
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                let v: f32 = kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(i, k)| {
+                        let sy = y + i as i32 - half;
+                        if (0..height).contains(&sy) {
+                            k * horizontal[self.index(x as u32, sy as u32)]
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum();
+                let i = self.index(x as u32, y as u32);
+                self.data[i] = v.clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    pub fn multiply(&mut self, other: &Mask) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = self.index(x, y);
+                self.data[i] *= other.value(x as i32, y as i32);
+            }
+        }
+    }
+}
+
+fn area(width: u32, height: u32) -> usize {
+    (width as usize)
+        .checked_mul(height as usize)
+        .expect("mask is too large")
+}
+
+impl Canvas for Mask {
+    type Pixel = Luma<f32>;
+
+    fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Luma<f32> {
+        Luma([self.value(x as i32, y as i32)])
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Luma<f32>) {
+        self.set_value(x, y, color[0]);
+    }
+}
+
+pub struct Masked<'a, C> {
+    canvas: &'a mut C,
+    mask: &'a Mask,
+    offset: (i32, i32),
+}
+
+impl<'a, C: Canvas> Masked<'a, C> {
+    pub fn new(canvas: &'a mut C, mask: &'a Mask) -> Masked<'a, C> {
+        Masked {
+            canvas,
+            mask,
+            offset: (0, 0),
+        }
+    }
+
+    pub fn with_offset(mut self, x: i32, y: i32) -> Masked<'a, C> {
+        self.offset = (x, y);
+        self
+    }
+
+    fn coverage(&self, x: u32, y: u32) -> f32 {
+        self.mask.value(
+            (x as i32).saturating_sub(self.offset.0),
+            (y as i32).saturating_sub(self.offset.1),
+        )
+    }
+}
+
+impl<C> Canvas for Masked<'_, C>
+where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    type Pixel = C::Pixel;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.canvas.dimensions()
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+        self.canvas.get_pixel(x, y)
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Self::Pixel) {
+        let coverage = self.coverage(x, y);
+        if coverage >= 1.0 {
+            self.canvas.draw_pixel(x, y, color);
+        } else if coverage > 0.0 {
+            self.canvas.blend_pixel(x, y, color, coverage);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Self::Pixel, coverage: f32) {
+        let coverage = coverage * self.coverage(x, y);
+        if coverage > 0.0 {
+            self.canvas.blend_pixel(x, y, color, coverage);
+        }
+    }
+
+    fn composite_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        color: Self::Pixel,
+        mode: BlendMode,
+        coverage: f32,
+    ) {
+        let coverage = coverage * self.coverage(x, y);
+        if coverage > 0.0 {
+            self.canvas.composite_pixel(x, y, color, mode, coverage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, ImageBuffer, LumaA, Rgba, RgbaImage};
+
+    #[test]
+    fn values_are_clamped_and_zero_outside() {
+        let mut mask = Mask::filled(2, 2, 1.5);
+        assert_eq!(mask.value(1, 1), 1.0);
+        mask.set_value(0, 1, -1.0);
+        assert_eq!(mask.value(0, 1), 0.0);
+        assert_eq!(mask.value(-1, 0), 0.0);
+        assert_eq!(mask.value(0, 2), 0.0);
+        assert_eq!(mask.value(i32::MAX, i32::MIN), 0.0);
+        mask.invert();
+        assert_eq!(mask.value(0, 1), 1.0);
+        assert_eq!(mask.value(1, 1), 0.0);
+    }
+
+    #[test]
+    fn alpha_and_luminance_masks() {
+        let image = RgbaImage::from_fn(2, 1, |x, _| {
+            if x == 0 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([255, 255, 255, 0])
+            }
+        });
+        let alpha = Mask::from_alpha(&image);
+        assert_eq!((alpha.value(0, 0), alpha.value(1, 0)), (1.0, 0.0));
+
+        let gray = ImageBuffer::from_fn(2, 1, |x, _| LumaA([(x * 255) as u8, 255]));
+        let luminance = Mask::from_luminance(&gray);
+        assert_eq!(luminance.value(0, 0), 0.0);
+        assert!((luminance.value(1, 0) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn blur_spreads_coverage() {
+        let mut mask = Mask::new(9, 9);
+        mask.set_value(4, 4, 1.0);
+        mask.blur(2.0);
+        assert!(mask.value(4, 4) < 1.0);
+        assert!(mask.value(3, 4) > 0.0);
+        assert_eq!(mask.value(3, 4), mask.value(5, 4));
+        assert_eq!(mask.value(4, 3), mask.value(4, 5));
+    }
+
+    #[test]
+    fn degenerate_blur_radii_are_bounded() {
+        let mut mask = Mask::filled(3, 3, 0.5);
+        let original = mask.clone();
+        mask.blur(0.0);
+        mask.blur(-4.0);
+        mask.blur(f32::NAN);
+        assert_eq!(mask, original);
+        mask.blur(f32::INFINITY);
+        assert!(mask.value(1, 1) > 0.0);
+        Mask::new(0, 0).blur(f32::INFINITY);
+    }
+
+    #[test]
+    fn multiply_combines_masks() {
+        let mut mask = Mask::filled(2, 1, 0.5);
+        let mut other = Mask::new(1, 1);
+        other.set_value(0, 0, 0.5);
+        mask.multiply(&other);
+        assert_eq!((mask.value(0, 0), mask.value(1, 0)), (0.25, 0.0));
+    }
+
+    #[test]
+    fn masked_canvas_scales_coverage() {
+        let mut mask = Mask::new(2, 1);
+        mask.set_value(0, 0, 1.0);
+        mask.set_value(1, 0, 0.5);
+        let mut image = GrayImage::new(4, 1);
+        {
+            let mut masked = Masked::new(&mut image, &mask).with_offset(1, 0);
+            for x in 0..4 {
+                masked.draw_pixel(x, 0, Luma([200u8]));
+            }
+        }
+        assert_eq!(image.into_raw(), vec![0, 200, 100, 0]);
+    }
+
+    #[test]
+    fn masked_offsets_do_not_overflow() {
+        let mask = Mask::filled(1, 1, 1.0);
+        let mut image = GrayImage::new(2, 1);
+        let mut masked = Masked::new(&mut image, &mask).with_offset(i32::MIN, i32::MAX);
+        masked.draw_pixel(1, 0, Luma([255u8]));
+        masked.blend_pixel(0, 0, Luma([255u8]), 1.0);
+        assert_eq!(image.into_raw(), vec![0, 0]);
+    }
+}
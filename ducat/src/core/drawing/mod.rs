@@ -1,34 +1,83 @@
 mod bezier;
-pub use self::bezier::{draw_cubic_bezier_curve, draw_cubic_bezier_curve_mut};
+pub use self::bezier::{
+    draw_cubic_bezier_curve, draw_cubic_bezier_curve_mut, draw_stroked_cubic_bezier_curve,
+    draw_stroked_cubic_bezier_curve_mut,
+};
+
+mod blit;
+pub use self::blit::{draw_image, draw_image_mut, draw_image_rect, draw_image_rect_mut};
 
 mod canvas;
-pub use self::canvas::{Blend, Canvas};
+pub use self::canvas::{Blend, Canvas, Composite, LinearLight};
+
+mod clip;
+pub use self::clip::{Clip, Clipped};
 
 mod conics;
 pub use self::conics::{
-    draw_filled_circle, draw_filled_circle_mut, draw_filled_ellipse, draw_filled_ellipse_mut,
-    draw_hollow_circle, draw_hollow_circle_mut, draw_hollow_ellipse, draw_hollow_ellipse_mut,
+    draw_antialiased_filled_circle, draw_antialiased_filled_circle_mut,
+    draw_antialiased_filled_ellipse, draw_antialiased_filled_ellipse_mut, draw_filled_circle,
+    draw_filled_circle_mut, draw_filled_ellipse, draw_filled_ellipse_mut, draw_hollow_circle,
+    draw_hollow_circle_mut, draw_hollow_ellipse, draw_hollow_ellipse_mut, draw_stroked_circle,
+    draw_stroked_circle_mut, draw_stroked_ellipse, draw_stroked_ellipse_mut,
 };
 
 mod cross;
 pub use self::cross::{draw_cross, draw_cross_mut};
 
+mod glyph_cache;
+pub use self::glyph_cache::{draw_text_cached, draw_text_cached_mut, GlyphCache};
+
+mod layer;
+pub use self::layer::LayerStack;
+
 mod line;
 pub use self::line::{
-    draw_antialiased_line_segment, draw_antialiased_line_segment_mut, draw_line_segment,
-    draw_line_segment_mut, BresenhamLineIter, BresenhamLinePixelIter, BresenhamLinePixelIterMut,
+    draw_antialiased_line_segment, draw_antialiased_line_segment_linear,
+    draw_antialiased_line_segment_linear_mut, draw_antialiased_line_segment_mut, draw_line_segment,
+    draw_line_segment_mut, draw_stroked_line_segment, draw_stroked_line_segment_mut,
+    BresenhamLineIter, BresenhamLinePixelIter, BresenhamLinePixelIterMut,
+};
+
+mod mask;
+pub use self::mask::{Mask, Masked};
+
+mod nine_slice;
+pub use self::nine_slice::{draw_nine_slice, draw_nine_slice_mut, NineSlice, SliceFill};
+
+mod path;
+pub use self::path::{
+    fill_path, fill_path_mut, fill_path_transformed, fill_path_transformed_mut, stroke_path,
+    stroke_path_mut, stroke_path_transformed, stroke_path_transformed_mut,
 };
 
 mod polygon;
-pub use self::polygon::{draw_polygon, draw_polygon_mut};
+pub use self::polygon::{
+    draw_antialiased_multi_polygon, draw_antialiased_multi_polygon_mut, draw_antialiased_polygon,
+    draw_antialiased_polygon_mut, draw_polygon, draw_polygon_mut, draw_stroked_polygon,
+    draw_stroked_polygon_mut, draw_stroked_polyline, draw_stroked_polyline_mut,
+};
+
+mod premultiplied;
+pub use self::premultiplied::PremultipliedImage;
+
+mod rasterizer;
+pub use self::rasterizer::{FillRule, Rasterizer};
 
 mod rect;
 pub use self::rect::{
-    draw_filled_rect, draw_filled_rect_mut, draw_hollow_rect, draw_hollow_rect_mut,
+    draw_antialiased_filled_rect, draw_antialiased_filled_rect_mut, draw_filled_rect,
+    draw_filled_rect_mut, draw_hollow_rect, draw_hollow_rect_mut, draw_stroked_rect,
+    draw_stroked_rect_mut,
 };
 
 mod text;
-pub use self::text::{draw_text, draw_text_mut, text_size};
+pub use self::text::{
+    draw_paragraph, draw_paragraph_mut, draw_styled_text, draw_styled_text_mut, draw_text,
+    draw_text_anchored, draw_text_anchored_mut, draw_text_mut, draw_text_on_path,
+    draw_text_on_path_mut, draw_text_paint, draw_text_paint_mut, draw_text_transformed,
+    draw_text_transformed_mut, draw_text_with_fallback, draw_text_with_fallback_mut, text_size,
+};
 
 fn draw_if_in_bounds<C>(canvas: &mut C, x: i32, y: i32, color: C::Pixel)
 where
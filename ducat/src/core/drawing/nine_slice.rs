@@ -0,0 +1,231 @@
+use crate::core::drawing::{draw_image_rect_mut, Canvas};
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::entity::rect::Rect;
+use crate::core::operation::sample::Interpolation;
+use conv::ValueInto;
+use image::{GenericImage, GenericImageView, ImageBuffer, Pixel};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum SliceFill {
+    #[default]
+    Stretch,
+    Tile,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NineSlice {
+    left: u32,
+    top: u32,
+    right: u32,
+    bottom: u32,
+    edges: SliceFill,
+    center: SliceFill,
+    interpolation: Interpolation,
+}
+
+impl NineSlice {
+    pub fn new(left: u32, top: u32, right: u32, bottom: u32) -> NineSlice {
+        NineSlice {
+            left,
+            top,
+            right,
+            bottom,
+            edges: SliceFill::Stretch,
+            center: SliceFill::Stretch,
+            interpolation: Interpolation::Bilinear,
+        }
+    }
+
+    pub fn uniform(inset: u32) -> NineSlice {
+        NineSlice::new(inset, inset, inset, inset)
+    }
+
+    pub fn with_edges(mut self, edges: SliceFill) -> NineSlice {
+        self.edges = edges;
+        self
+    }
+
+    pub fn with_center(mut self, center: SliceFill) -> NineSlice {
+        self.center = center;
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> NineSlice {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn insets(&self) -> (u32, u32, u32, u32) {
+        (self.left, self.top, self.right, self.bottom)
+    }
+
+    pub fn edges(&self) -> SliceFill {
+        self.edges
+    }
+
+    pub fn center(&self) -> SliceFill {
+        self.center
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+}
+
+pub fn draw_nine_slice<I, S>(image: &I, src: &S, slice: &NineSlice, rect: Rect) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    S: GenericImageView<Pixel = I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_nine_slice_mut(&mut out, src, slice, rect);
+    out
+}
+
+pub fn draw_nine_slice_mut<C, S>(canvas: &mut C, src: &S, slice: &NineSlice, rect: Rect)
+where
+    C: Canvas,
+    S: GenericImageView<Pixel = C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = src.dimensions();
+    let columns = spans(width, slice.left, slice.right, rect.left(), rect.width());
+    let rows = spans(height, slice.top, slice.bottom, rect.top(), rect.height());
+
+    for (j, &(sy, sh, dy, dh)) in rows.iter().enumerate() {
+        for (i, &(sx, sw, dx, dw)) in columns.iter().enumerate() {
+            if sw == 0 || sh == 0 || dw == 0 || dh == 0 {
+                continue;
+            }
+            let fill = match (i, j) {
+                (1, 1) => slice.center,
+                (1, _) | (_, 1) => slice.edges,
+                _ => SliceFill::Stretch,
+            };
+            let tile_x = fill == SliceFill::Tile && i == 1;
+            let tile_y = fill == SliceFill::Tile && j == 1;
+            let piece = src.view(sx, sy, sw, sh);
+            let target = Rect::at(dx, dy).of_size(dw, dh);
+            draw_piece(canvas, &piece, target, tile_x, tile_y, slice.interpolation);
+        }
+    }
+}
+
+fn spans(size: u32, start: u32, end: u32, origin: i32, length: u32) -> [(u32, u32, i32, u32); 3] {
+    let start = start.min(size);
+    let end = end.min(size - start);
+    let (dst_start, dst_end) = if start + end > length {
+        let dst_start = (start as f32 * length as f32 / (start + end) as f32).round() as u32;
+        (dst_start, length - dst_start)
+    } else {
+        (start, end)
+    };
+    let middle = size - start - end;
+    let dst_middle = length - dst_start - dst_end;
+    [
+        (0, start, origin, dst_start),
+        (start, middle, origin + dst_start as i32, dst_middle),
+        (
+            start + middle,
+            end,
+            origin + (dst_start + dst_middle) as i32,
+            dst_end,
+        ),
+    ]
+}
+
+fn draw_piece<C, S>(
+    canvas: &mut C,
+    piece: &S,
+    target: Rect,
+    tile_x: bool,
+    tile_y: bool,
+    interpolation: Interpolation,
+) where
+    C: Canvas,
+    S: GenericImageView<Pixel = C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = piece.dimensions();
+    let step_x = if tile_x { width } else { target.width() };
+    let step_y = if tile_y { height } else { target.height() };
+
+    let mut y = 0;
+    while y < target.height() {
+        let th = step_y.min(target.height() - y);
+        let sh = if tile_y { th } else { height };
+        let mut x = 0;
+        while x < target.width() {
+            let tw = step_x.min(target.width() - x);
+            let sw = if tile_x { tw } else { width };
+            let tile = Rect::at(target.left() + x as i32, target.top() + y as i32).of_size(tw, th);
+            draw_image_rect_mut(canvas, &piece.view(0, 0, sw, sh), tile, interpolation, 1.0);
+            x += step_x;
+        }
+        y += step_y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn spans_keep_insets_and_stretch_the_middle() {
+        assert_eq!(
+            spans(10, 3, 2, 5, 20),
+            [(0, 3, 5, 3), (3, 5, 8, 15), (8, 2, 23, 2)]
+        );
+    }
+
+    #[test]
+    fn spans_shrink_insets_that_do_not_fit() {
+        assert_eq!(
+            spans(10, 4, 4, 0, 4),
+            [(0, 4, 0, 2), (4, 2, 2, 0), (6, 4, 2, 2)]
+        );
+        assert_eq!(
+            spans(10, 3, 1, 0, 2),
+            [(0, 3, 0, 2), (3, 6, 2, 0), (9, 1, 2, 0)]
+        );
+    }
+
+    #[test]
+    fn spans_clamp_insets_to_the_source() {
+        assert_eq!(
+            spans(6, 5, 5, 0, 20),
+            [(0, 5, 0, 5), (5, 0, 5, 14), (5, 1, 19, 1)]
+        );
+        assert_eq!(
+            spans(0, 2, 2, 0, 10),
+            [(0, 0, 0, 0), (0, 0, 0, 10), (0, 0, 10, 0)]
+        );
+    }
+
+    #[test]
+    fn corners_are_copied_unscaled() {
+        let src = GrayImage::from_fn(3, 3, |x, y| Luma([(10 * (y * 3 + x) + 10) as u8]));
+        let slice = NineSlice::uniform(1).with_interpolation(Interpolation::Nearest);
+        let out = draw_nine_slice(
+            &GrayImage::new(6, 5),
+            &src,
+            &slice,
+            Rect::at(0, 0).of_size(6, 5),
+        );
+        assert_eq!(out.get_pixel(0, 0)[0], 10);
+        assert_eq!(out.get_pixel(5, 0)[0], 30);
+        assert_eq!(out.get_pixel(0, 4)[0], 70);
+        assert_eq!(out.get_pixel(5, 4)[0], 90);
+        for x in 1..5 {
+            assert_eq!(out.get_pixel(x, 0)[0], 20);
+            assert_eq!(out.get_pixel(x, 2)[0], 50);
+        }
+        for y in 1..4 {
+            assert_eq!(out.get_pixel(0, y)[0], 40);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::core::drawing::{Canvas, FillRule, Rasterizer};
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::entity::rect::Rect;
+use crate::core::entity::transform::Transform2D;
+use crate::core::paint::{Paint, TransformedPaint};
+use crate::core::path::{Path, StrokeStyle, FLATTEN_TOLERANCE};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
+
+pub fn fill_path<I, T>(image: &I, path: &Path, fill_rule: FillRule, paint: T) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    fill_path_mut(&mut out, path, fill_rule, paint);
+    out
+}
+
+pub fn fill_path_mut<C, T>(canvas: &mut C, path: &Path, fill_rule: FillRule, paint: T)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = canvas.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let canvas_bounds = Rect::at(0, 0).of_size(width, height);
+    let region = match path
+        .bounds()
+        .and_then(|bounds| bounds.to_rect_within(canvas_bounds))
+    {
+        Some(region) => region,
+        None => return,
+    };
+
+    let mut rasterizer = Rasterizer::new(region);
+    rasterizer.add_path(path);
+    rasterizer.for_each_pixel(fill_rule, |x, y, coverage| {
+        let (cx, cy) = (x as f32 + 0.5, y as f32 + 0.5);
+        let coverage = coverage * paint.coverage_at(cx, cy);
+        if coverage > 0.0 {
+            canvas.blend_pixel(x as u32, y as u32, paint.color_at(cx, cy), coverage);
+        }
+    });
+}
+
+pub fn stroke_path<I, T>(image: &I, path: &Path, style: &StrokeStyle, paint: T) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    stroke_path_mut(&mut out, path, style, paint);
+    out
+}
+
+pub fn stroke_path_mut<C, T>(canvas: &mut C, path: &Path, style: &StrokeStyle, paint: T)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fill_path_mut(canvas, &path.stroke(style), FillRule::NonZero, paint);
+}
+
+pub fn fill_path_transformed<I, T>(
+    image: &I,
+    path: &Path,
+    transform: &Transform2D,
+    fill_rule: FillRule,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    fill_path_transformed_mut(&mut out, path, transform, fill_rule, paint);
+    out
+}
+
+pub fn fill_path_transformed_mut<C, T>(
+    canvas: &mut C,
+    path: &Path,
+    transform: &Transform2D,
+    fill_rule: FillRule,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fill_path_mut(
+        canvas,
+        &path.transform(transform),
+        fill_rule,
+        TransformedPaint::new(paint, *transform),
+    );
+}
+
+pub fn stroke_path_transformed<I, T>(
+    image: &I,
+    path: &Path,
+    transform: &Transform2D,
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    stroke_path_transformed_mut(&mut out, path, transform, style, paint);
+    out
+}
+
+pub fn stroke_path_transformed_mut<C, T>(
+    canvas: &mut C,
+    path: &Path,
+    transform: &Transform2D,
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let scale = transform.max_scale();
+    if scale <= 0.0 || !scale.is_finite() {
+        return;
+    }
+    let outline = path.stroke_with_tolerance(style, FLATTEN_TOLERANCE / scale);
+    fill_path_transformed_mut(canvas, &outline, transform, FillRule::NonZero, paint);
+}
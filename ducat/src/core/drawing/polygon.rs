@@ -1,11 +1,14 @@
 use crate::core::drawing::line::draw_line_segment_mut;
-use crate::core::drawing::Canvas;
-use crate::core::entity::definitions::Image;
+use crate::core::drawing::path::{fill_path_mut, stroke_path_mut};
+use crate::core::drawing::{Canvas, FillRule};
+use crate::core::entity::definitions::{Clamp, Image};
 use crate::core::entity::point::Point;
-use image::{GenericImage, ImageBuffer};
+use crate::core::paint::Paint;
+use crate::core::path::{Path, StrokeStyle};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
 use std::cmp::{max, min};
 use std::f32;
-use std::i32;
 
 pub fn draw_polygon<I>(image: &I, poly: &[Point<i32>], color: I::Pixel) -> Image<I::Pixel>
 where
@@ -46,7 +49,7 @@ where
     y_min = max(0, min(y_min, height as i32 - 1));
     y_max = max(0, min(y_max, height as i32 - 1));
 
-    let mut closed: Vec<Point<i32>> = poly.iter().copied().collect();
+    let mut closed: Vec<Point<i32>> = poly.to_vec();
     closed.push(poly[0]);
 
     let edges: Vec<&[Point<i32>]> = closed.windows(2).collect();
@@ -99,3 +102,139 @@ where
         draw_line_segment_mut(canvas, start, end, color);
     }
 }
+
+pub fn draw_antialiased_polygon<I, T>(
+    image: &I,
+    poly: &[Point<f32>],
+    fill_rule: FillRule,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_antialiased_polygon_mut(&mut out, poly, fill_rule, paint);
+    out
+}
+
+pub fn draw_antialiased_polygon_mut<C, T>(
+    canvas: &mut C,
+    poly: &[Point<f32>],
+    fill_rule: FillRule,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_polygon(poly);
+    fill_path_mut(canvas, &path, fill_rule, paint);
+}
+
+pub fn draw_antialiased_multi_polygon<I, T>(
+    image: &I,
+    contours: &[Vec<Point<f32>>],
+    fill_rule: FillRule,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_antialiased_multi_polygon_mut(&mut out, contours, fill_rule, paint);
+    out
+}
+
+pub fn draw_antialiased_multi_polygon_mut<C, T>(
+    canvas: &mut C,
+    contours: &[Vec<Point<f32>>],
+    fill_rule: FillRule,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    for contour in contours {
+        path.add_polygon(contour);
+    }
+    fill_path_mut(canvas, &path, fill_rule, paint);
+}
+
+pub fn draw_stroked_polyline<I, T>(
+    image: &I,
+    points: &[Point<f32>],
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_polyline_mut(&mut out, points, style, paint);
+    out
+}
+
+pub fn draw_stroked_polyline_mut<C, T>(
+    canvas: &mut C,
+    points: &[Point<f32>],
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    for p in points {
+        path.line_to(p.x, p.y);
+    }
+    stroke_path_mut(canvas, &path, style, paint);
+}
+
+pub fn draw_stroked_polygon<I, T>(
+    image: &I,
+    poly: &[Point<f32>],
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_polygon_mut(&mut out, poly, style, paint);
+    out
+}
+
+pub fn draw_stroked_polygon_mut<C, T>(
+    canvas: &mut C,
+    poly: &[Point<f32>],
+    style: &StrokeStyle,
+    paint: T,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_polygon(poly);
+    stroke_path_mut(canvas, &path, style, paint);
+}
@@ -0,0 +1,141 @@
+use crate::core::drawing::Canvas;
+use crate::core::entity::definitions::{Clamp, Image};
+use crate::core::operation::composite::{composite_premultiplied, BlendMode};
+use crate::core::operation::premultiply::{premultiply, unpremultiply};
+use conv::ValueInto;
+use image::{GenericImageView, ImageBuffer, Pixel};
+use std::marker::PhantomData;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PremultipliedImage<P> {
+    width: u32,
+    height: u32,
+    data: Vec<[f32; 4]>,
+    pixel: PhantomData<P>,
+}
+
+impl<P: Pixel> PremultipliedImage<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    pub fn new(width: u32, height: u32) -> PremultipliedImage<P> {
+        PremultipliedImage {
+            width,
+            height,
+            data: vec![[0.0; 4]; area(width, height)],
+            pixel: PhantomData,
+        }
+    }
+
+    pub fn from_image<I: GenericImageView<Pixel = P>>(image: &I) -> PremultipliedImage<P> {
+        let (width, height) = image.dimensions();
+        let mut data = Vec::with_capacity(area(width, height));
+        for y in 0..height {
+            for x in 0..width {
+                data.push(premultiply(&image.get_pixel(x, y)));
+            }
+        }
+        PremultipliedImage {
+            width,
+            height,
+            data,
+            pixel: PhantomData,
+        }
+    }
+
+    pub fn to_image(&self) -> Image<P>
+    where
+        P: 'static,
+    {
+        ImageBuffer::from_fn(self.width, self.height, |x, y| {
+            unpremultiply(self.premultiplied_pixel(x, y))
+        })
+    }
+
+    pub fn premultiplied_pixel(&self, x: u32, y: u32) -> [f32; 4] {
+        self.data[self.index(x, y)]
+    }
+
+    pub fn put_premultiplied_pixel(&mut self, x: u32, y: u32, rgba: [f32; 4]) {
+        let i = self.index(x, y);
+        self.data[i] = rgba;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    pub fn clear(&mut self) {
+        self.data.iter_mut().for_each(|p| *p = [0.0; 4]);
+    }
+}
+
+fn area(width: u32, height: u32) -> usize {
+    (width as usize)
+        .checked_mul(height as usize)
+        .expect("image is too large")
+}
+
+impl<P: Pixel> Canvas for PremultipliedImage<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    type Pixel = P;
+
+    fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> P {
+        unpremultiply(self.premultiplied_pixel(x, y))
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: P) {
+        self.put_premultiplied_pixel(x, y, premultiply(&color));
+    }
+
+    fn blend_pixel(&mut self, x: u32, y: u32, color: P, coverage: f32) {
+        self.composite_pixel(x, y, color, BlendMode::SourceOver, coverage);
+    }
+
+    fn composite_pixel(&mut self, x: u32, y: u32, color: P, mode: BlendMode, coverage: f32) {
+        let dst = self.premultiplied_pixel(x, y);
+        let out = composite_premultiplied(premultiply(&color), dst, mode, coverage);
+        self.put_premultiplied_pixel(x, y, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn blending_composites_source_over() {
+        let mut image = PremultipliedImage::<Rgba<u8>>::new(1, 1);
+        image.draw_pixel(0, 0, Rgba([0, 0, 255, 255]));
+        image.blend_pixel(0, 0, Rgba([255, 0, 0, 128]), 1.0);
+        let [r, g, b, a] = image.premultiplied_pixel(0, 0);
+        assert!((r - 128.0 / 255.0).abs() < 1e-6);
+        assert_eq!(g, 0.0);
+        assert!((b - 127.0 / 255.0).abs() < 1e-6);
+        assert_eq!(a, 1.0);
+    }
+
+    #[test]
+    fn repeated_blends_stay_in_float() {
+        let mut image = PremultipliedImage::<Rgba<u8>>::new(1, 1);
+        for _ in 0..8 {
+            image.blend_pixel(0, 0, Rgba([255, 255, 255, 255]), 0.01);
+        }
+        let expected = 1.0 - 0.99f32.powi(8);
+        assert!((image.premultiplied_pixel(0, 0)[3] - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn round_trip_through_straight_alpha() {
+        let source = ImageBuffer::from_fn(4, 1, |x, _| Rgba([200u8, 100, 50, (x * 60 + 60) as u8]));
+        let image = PremultipliedImage::from_image(&source);
+        assert_eq!(image.to_image(), source);
+    }
+}
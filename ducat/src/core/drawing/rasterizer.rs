@@ -0,0 +1,325 @@
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+use crate::core::path::{Path, FLATTEN_TOLERANCE};
+
+const MIN_COVERAGE: f32 = 1.0 / 512.0;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+impl FillRule {
+    fn coverage(self, winding: f32) -> f32 {
+        match self {
+            FillRule::NonZero => winding.abs().min(1.0),
+            FillRule::EvenOdd => {
+                let parity = winding.abs() % 2.0;
+                if parity > 1.0 {
+                    2.0 - parity
+                } else {
+                    parity
+                }
+            }
+        }
+    }
+}
+
+pub struct Rasterizer {
+    left: i32,
+    top: i32,
+    width: usize,
+    height: usize,
+    accumulation: Vec<f32>,
+}
+
+impl Rasterizer {
+    pub fn new(region: Rect) -> Rasterizer {
+        let width = region.width() as usize;
+        let height = region.height() as usize;
+        Rasterizer {
+            left: region.left(),
+            top: region.top(),
+            width,
+            height,
+            accumulation: vec![0.0; (width + 2) * height],
+        }
+    }
+
+    pub fn region(&self) -> Rect {
+        Rect::at(self.left, self.top).of_size(self.width as u32, self.height as u32)
+    }
+
+    pub fn clear(&mut self) {
+        self.accumulation.iter_mut().for_each(|a| *a = 0.0);
+    }
+
+    pub fn add_path(&mut self, path: &Path) {
+        for polyline in path.flatten(FLATTEN_TOLERANCE) {
+            let points = &polyline.points;
+            for i in 0..points.len() {
+                self.add_line(points[i], points[(i + 1) % points.len()]);
+            }
+        }
+    }
+
+    pub fn add_line(&mut self, start: Point<f32>, end: Point<f32>) {
+        let p0 = Point::new(start.x - self.left as f32, start.y - self.top as f32);
+        let p1 = Point::new(end.x - self.left as f32, end.y - self.top as f32);
+        let (width, height) = (self.width as f32, self.height as f32);
+
+        if ![p0.x, p0.y, p1.x, p1.y].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        if p0.y == p1.y || (p0.y <= 0.0 && p1.y <= 0.0) || (p0.y >= height && p1.y >= height) {
+            return;
+        }
+
+        let mut splits = [0.0, 1.0, 1.0, 1.0];
+        let mut count = 1;
+        for &boundary in &[0.0, width] {
+            if (p0.x - boundary) * (p1.x - boundary) < 0.0 {
+                splits[count] = (boundary - p0.x) / (p1.x - p0.x);
+                count += 1;
+            }
+        }
+        splits[..count + 1].sort_by(|a, b| a.total_cmp(b));
+
+        let at = |t: f32| {
+            Point::new(
+                (p0.x + (p1.x - p0.x) * t).clamp(0.0, width),
+                p0.y + (p1.y - p0.y) * t,
+            )
+        };
+        for pair in splits[..count + 1].windows(2) {
+            self.accumulate(at(pair[0]), at(pair[1]));
+        }
+    }
+
+    pub fn for_each_pixel<F>(&self, fill_rule: FillRule, mut f: F)
+    where
+        F: FnMut(i32, i32, f32),
+    {
+        let stride = self.width + 2;
+        for y in 0..self.height {
+            let mut acc = 0.0;
+            for x in 0..self.width {
+                acc += self.accumulation[y * stride + x];
+                let mut coverage = fill_rule.coverage(acc);
+                if coverage < MIN_COVERAGE {
+                    continue;
+                }
+                if coverage > 1.0 - MIN_COVERAGE {
+                    coverage = 1.0;
+                }
+                f(self.left + x as i32, self.top + y as i32, coverage);
+            }
+        }
+    }
+
+    fn accumulate(&mut self, p0: Point<f32>, p1: Point<f32>) {
+        let (dir, p0, p1) = if p0.y < p1.y {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+
+        let y_start = p0.y.max(0.0);
+        let y_end = p1.y.min(self.height as f32);
+        if y_start >= y_end {
+            return;
+        }
+
+        let width = self.width as f32;
+        let stride = self.width + 2;
+        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+        let mut x = (p0.x + (y_start - p0.y) * dxdy).clamp(0.0, width);
+
+        for y in (y_start as usize)..(y_end.ceil() as usize) {
+            let row = y * stride;
+            let dy = ((y + 1) as f32).min(y_end) - (y as f32).max(y_start);
+            let x_next = (x + dxdy * dy).clamp(0.0, width);
+            let d = dy * dir;
+
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+
+            if x1i <= x0i + 1 {
+                let xmf = 0.5 * (x + x_next) - x0_floor;
+                self.accumulation[row + x0i] += d - d * xmf;
+                self.accumulation[row + x0i + 1] += d * xmf;
+            } else {
+                let s = (x1 - x0).recip();
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+
+                self.accumulation[row + x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    self.accumulation[row + x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    self.accumulation[row + x0i + 1] += d * (a1 - a0);
+                    for xi in x0i + 2..x1i - 1 {
+                        self.accumulation[row + xi] += d * s;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    self.accumulation[row + x1i - 1] += d * (1.0 - a2 - am);
+                }
+                self.accumulation[row + x1i] += d * am;
+            }
+
+            x = x_next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::drawing::fill_path_mut;
+    use image::{GrayImage, Luma};
+
+    fn coverage(path: &Path, region: Rect, fill_rule: FillRule) -> Vec<f32> {
+        let mut rasterizer = Rasterizer::new(region);
+        rasterizer.add_path(path);
+        let mut out = vec![0.0; (region.width() * region.height()) as usize];
+        rasterizer.for_each_pixel(fill_rule, |x, y, c| {
+            let i = (y - region.top()) as u32 * region.width() + (x - region.left()) as u32;
+            out[i as usize] = c;
+        });
+        out
+    }
+
+    #[test]
+    fn fractional_rect_has_partial_edge_coverage() {
+        let mut path = Path::new();
+        path.add_rect(0.5, 0.0, 2.75, 1.0);
+        let row = coverage(&path, Rect::at(0, 0).of_size(6, 1), FillRule::NonZero);
+        assert_eq!(row, vec![0.5, 1.0, 1.0, 0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn fractional_rect_blends_into_image() {
+        let mut image = GrayImage::new(6, 1);
+        let mut path = Path::new();
+        path.add_rect(0.5, 0.0, 2.75, 1.0);
+        fill_path_mut(&mut image, &path, FillRule::NonZero, Luma([255u8]));
+        assert_eq!(image.into_raw(), vec![127, 255, 255, 63, 0, 0]);
+    }
+
+    #[test]
+    fn circle_coverage_matches_area() {
+        let mut path = Path::new();
+        path.add_circle(32.0, 32.0, 30.0);
+        let total: f32 = coverage(&path, Rect::at(0, 0).of_size(64, 64), FillRule::NonZero)
+            .iter()
+            .sum();
+        let expected = std::f32::consts::PI * 30.0 * 30.0;
+        assert!((total - expected).abs() < expected * 0.005, "{}", total);
+    }
+
+    #[test]
+    fn lines_outside_region_are_clipped() {
+        let mut path = Path::new();
+        path.add_rect(-10.0, -10.0, 14.0, 30.0);
+        let cells = coverage(&path, Rect::at(0, 0).of_size(8, 4), FillRule::NonZero);
+        for row in cells.chunks(8) {
+            assert_eq!(row, &[1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn non_finite_edges_are_skipped() {
+        let mut path = Path::new();
+        path.move_to(f32::NAN, 0.0)
+            .line_to(4.0, f32::INFINITY)
+            .line_to(2.0, 3.0)
+            .close();
+        let cells = coverage(&path, Rect::at(0, 0).of_size(4, 4), FillRule::NonZero);
+        assert!(cells.iter().all(|c| c.is_finite()));
+    }
+
+    #[test]
+    fn zero_sized_canvas_is_ignored() {
+        let mut image = GrayImage::new(0, 0);
+        let mut path = Path::new();
+        path.add_rect(0.0, 0.0, 4.0, 4.0);
+        fill_path_mut(&mut image, &path, FillRule::NonZero, Luma([255u8]));
+    }
+
+    #[test]
+    fn huge_paths_are_limited_to_the_canvas() {
+        let mut image = GrayImage::new(10, 10);
+        let mut path = Path::new();
+        path.add_rect(-3e9, 0.0, 6e9, 5.0);
+        fill_path_mut(&mut image, &path, FillRule::NonZero, Luma([255u8]));
+        for (y, row) in image.into_raw().chunks(10).enumerate() {
+            let expected = if y < 5 { 255 } else { 0 };
+            assert!(row.iter().all(|v| *v == expected), "row {}", y);
+        }
+    }
+
+    fn square(x: f32, y: f32, size: f32, clockwise: bool) -> Vec<Point<f32>> {
+        let mut points = vec![
+            Point::new(x, y),
+            Point::new(x + size, y),
+            Point::new(x + size, y + size),
+            Point::new(x, y + size),
+        ];
+        if !clockwise {
+            points.reverse();
+        }
+        points
+    }
+
+    fn hole_coverage(inner_clockwise: bool, fill_rule: FillRule) -> (f32, f32) {
+        let mut path = Path::new();
+        path.add_polygon(&square(0.0, 0.0, 12.0, true));
+        path.add_polygon(&square(4.0, 4.0, 4.0, inner_clockwise));
+        let cells = coverage(&path, Rect::at(0, 0).of_size(12, 12), fill_rule);
+        (cells[12 + 1], cells[6 * 12 + 6])
+    }
+
+    #[test]
+    fn even_odd_hole_is_empty() {
+        assert_eq!(hole_coverage(true, FillRule::EvenOdd), (1.0, 0.0));
+        assert_eq!(hole_coverage(false, FillRule::EvenOdd), (1.0, 0.0));
+    }
+
+    #[test]
+    fn non_zero_hole_depends_on_direction() {
+        assert_eq!(hole_coverage(true, FillRule::NonZero), (1.0, 1.0));
+        assert_eq!(hole_coverage(false, FillRule::NonZero), (1.0, 0.0));
+    }
+
+    #[test]
+    fn self_intersecting_star() {
+        let star: Vec<Point<f32>> = (0..5)
+            .map(|i| {
+                let angle = i as f32 * 4.0 * std::f32::consts::PI / 5.0;
+                Point::new(20.0 + 18.0 * angle.sin(), 20.0 - 18.0 * angle.cos())
+            })
+            .collect();
+        let mut path = Path::new();
+        path.add_polygon(&star);
+        let region = Rect::at(0, 0).of_size(40, 40);
+        let center = 20 * 40 + 20;
+        assert_eq!(coverage(&path, region, FillRule::NonZero)[center], 1.0);
+        assert_eq!(coverage(&path, region, FillRule::EvenOdd)[center], 0.0);
+    }
+
+    #[test]
+    fn even_odd_folds_partial_winding() {
+        assert_eq!(FillRule::EvenOdd.coverage(0.25), 0.25);
+        assert_eq!(FillRule::EvenOdd.coverage(1.75), 0.25);
+        assert_eq!(FillRule::EvenOdd.coverage(-2.0), 0.0);
+        assert_eq!(FillRule::NonZero.coverage(-2.0), 1.0);
+    }
+}
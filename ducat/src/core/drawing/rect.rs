@@ -1,8 +1,13 @@
 use crate::core::drawing::line::draw_line_segment_mut;
-use crate::core::drawing::Canvas;
-use crate::core::entity::definitions::Image;
+use crate::core::drawing::path::{fill_path_mut, stroke_path_mut};
+use crate::core::drawing::{Canvas, FillRule};
+use crate::core::entity::bounds::Bounds;
+use crate::core::entity::definitions::{Clamp, Image};
 use crate::core::entity::rect::Rect;
-use image::{GenericImage, ImageBuffer};
+use crate::core::paint::Paint;
+use crate::core::path::{Path, StrokeStyle};
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
 use std::f32;
 
 pub fn draw_hollow_rect<I>(image: &I, rect: Rect, color: I::Pixel) -> Image<I::Pixel>
@@ -59,3 +64,61 @@ where
         }
     }
 }
+
+pub fn draw_antialiased_filled_rect<I, T>(image: &I, rect: Bounds, paint: T) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_antialiased_filled_rect_mut(&mut out, rect, paint);
+    out
+}
+
+pub fn draw_antialiased_filled_rect_mut<C, T>(canvas: &mut C, rect: Bounds, paint: T)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_rect(rect.min.x, rect.min.y, rect.width(), rect.height());
+    fill_path_mut(canvas, &path, FillRule::NonZero, paint);
+}
+
+pub fn draw_stroked_rect<I, T>(
+    image: &I,
+    rect: Rect,
+    style: &StrokeStyle,
+    paint: T,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_stroked_rect_mut(&mut out, rect, style, paint);
+    out
+}
+
+pub fn draw_stroked_rect_mut<C, T>(canvas: &mut C, rect: Rect, style: &StrokeStyle, paint: T)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut path = Path::new();
+    path.add_rect(
+        rect.left() as f32 + 0.5,
+        rect.top() as f32 + 0.5,
+        (rect.width() - 1) as f32,
+        (rect.height() - 1) as f32,
+    );
+    stroke_path_mut(canvas, &path, style, paint);
+}
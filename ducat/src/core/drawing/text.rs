@@ -1,18 +1,28 @@
-use crate::core::drawing::Canvas;
+use crate::core::drawing::{
+    fill_path_mut, fill_path_transformed_mut, Canvas, FillRule, Mask, Masked,
+};
+use crate::core::entity::bounds::Bounds;
 use crate::core::entity::definitions::{Clamp, Image};
-use crate::core::operation::pixelops::weighted_sum;
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+use crate::core::entity::transform::Transform2D;
+use crate::core::paint::{Paint, Solid};
+use crate::core::path::{LineJoin, Path, StrokeStyle, FLATTEN_TOLERANCE};
+use crate::core::text::{
+    measure, shape, text_outline, text_path_outline, FontCollection, FontSource, PathTextStyle,
+    TextAnchor, TextLayout, TextShadow, TextStyle,
+};
 use conv::ValueInto;
-use image::{GenericImage, ImageBuffer, Pixel};
-use rusttype::{point, Font, PositionedGlyph, Rect, Scale};
+use image::{GenericImage, ImageBuffer, Luma, Pixel};
+use rusttype::{point, Font, PositionedGlyph, Scale};
 use std::cmp::max;
 use std::f32;
-use std::i32;
 
 fn layout_glyphs(
     scale: Scale,
     font: &Font,
     text: &str,
-    mut f: impl FnMut(PositionedGlyph, Rect<i32>),
+    mut f: impl FnMut(PositionedGlyph, rusttype::Rect<i32>),
 ) -> (i32, i32) {
     let v_metrics = font.v_metrics(scale);
 
@@ -45,23 +55,56 @@ pub fn draw_text_mut<'a, C>(
     C: Canvas,
     <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
 {
+    draw_text_paint_mut(canvas, Solid(color), x, y, scale, font, text);
+}
+
+pub fn draw_text_paint_mut<'a, C, T>(
+    canvas: &'a mut C,
+    paint: T,
+    x: i32,
+    y: i32,
+    scale: Scale,
+    font: &'a Font<'a>,
+    text: &'a str,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    layout_glyphs(scale, font, text, |g, _| {
+        draw_glyph(canvas, &paint, &g, x, y)
+    });
+}
+
+fn draw_glyph<C, T>(canvas: &mut C, paint: &T, glyph: &PositionedGlyph, x: i32, y: i32)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let bb = match glyph.pixel_bounding_box() {
+        Some(bb) => bb,
+        None => return,
+    };
     let image_width = canvas.width() as i32;
     let image_height = canvas.height() as i32;
 
-    layout_glyphs(scale, font, text, |g, bb| {
-        g.draw(|gx, gy, gv| {
-            let gx = gx as i32 + bb.min.x;
-            let gy = gy as i32 + bb.min.y;
-
-            let image_x = gx + x;
-            let image_y = gy + y;
+    glyph.draw(|gx, gy, gv| {
+        let image_x = gx as i32 + bb.min.x + x;
+        let image_y = gy as i32 + bb.min.y + y;
 
-            if (0..image_width).contains(&image_x) && (0..image_height).contains(&image_y) {
-                let pixel = canvas.get_pixel(image_x as u32, image_y as u32);
-                let weighted_color = weighted_sum(pixel, color, 1.0 - gv, gv);
-                canvas.draw_pixel(image_x as u32, image_y as u32, weighted_color);
+        if (0..image_width).contains(&image_x) && (0..image_height).contains(&image_y) {
+            let (cx, cy) = (image_x as f32 + 0.5, image_y as f32 + 0.5);
+            let coverage = gv * paint.coverage_at(cx, cy);
+            if coverage > 0.0 {
+                canvas.blend_pixel(
+                    image_x as u32,
+                    image_y as u32,
+                    paint.color_at(cx, cy),
+                    coverage,
+                );
             }
-        })
+        }
     });
 }
 
@@ -84,3 +127,588 @@ where
     draw_text_mut(&mut out, color, x, y, scale, font, text);
     out
 }
+
+pub fn draw_text_paint<'a, I, T>(
+    image: &'a mut I,
+    paint: T,
+    x: i32,
+    y: i32,
+    scale: Scale,
+    font: &'a Font<'a>,
+    text: &'a str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_paint_mut(&mut out, paint, x, y, scale, font, text);
+    out
+}
+
+pub fn draw_text_with_fallback_mut<C, T>(
+    canvas: &mut C,
+    paint: T,
+    x: i32,
+    y: i32,
+    scale: Scale,
+    fonts: &FontCollection,
+    text: &str,
+) where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let ascent = match fonts.primary() {
+        Some(font) => font.v_metrics(scale).ascent,
+        None => return,
+    };
+    for g in fonts.layout(text, scale, point(0.0, ascent)) {
+        draw_glyph(canvas, &paint, &g, x, y);
+    }
+}
+
+pub fn draw_text_with_fallback<I, T>(
+    image: &I,
+    paint: T,
+    x: i32,
+    y: i32,
+    scale: Scale,
+    fonts: &FontCollection,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_with_fallback_mut(&mut out, paint, x, y, scale, fonts, text);
+    out
+}
+
+pub fn draw_paragraph_mut<C, T>(canvas: &mut C, paint: T, rect: Rect, layout: &TextLayout)
+where
+    C: Canvas,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (dx, dy) = layout.offset_in(rect.width() as f32, rect.height() as f32);
+    let x = rect.left() + dx.round() as i32;
+    let y = rect.top() + dy.round() as i32;
+    for glyph in layout.glyphs() {
+        draw_glyph(canvas, &paint, glyph, x, y);
+    }
+}
+
+pub fn draw_paragraph<I, T>(image: &I, paint: T, rect: Rect, layout: &TextLayout) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_paragraph_mut(&mut out, paint, rect, layout);
+    out
+}
+
+pub fn draw_text_anchored_mut<'font, C, T, F>(
+    canvas: &mut C,
+    paint: T,
+    position: Point<f32>,
+    anchor: TextAnchor,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) where
+    C: Canvas,
+    F: FontSource<'font>,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (dx, dy) = match anchor_offset(anchor, scale, font, text) {
+        Some(offset) => offset,
+        None => return,
+    };
+    for shaped in shape(font, scale, text) {
+        let glyph = shaped
+            .glyph
+            .positioned(point(position.x + dx + shaped.x, position.y + dy));
+        draw_glyph(canvas, &paint, &glyph, 0, 0);
+    }
+}
+
+pub fn draw_text_anchored<'font, I, T, F>(
+    image: &I,
+    paint: T,
+    position: Point<f32>,
+    anchor: TextAnchor,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    F: FontSource<'font>,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_anchored_mut(&mut out, paint, position, anchor, scale, font, text);
+    out
+}
+
+pub fn draw_text_transformed_mut<'font, C, T, F>(
+    canvas: &mut C,
+    paint: T,
+    transform: &Transform2D,
+    anchor: TextAnchor,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) where
+    C: Canvas,
+    F: FontSource<'font>,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (dx, dy) = match anchor_offset(anchor, scale, font, text) {
+        Some(offset) => offset,
+        None => return,
+    };
+    let outline = text_outline(scale, font, text, Point::new(dx, dy));
+    fill_path_transformed_mut(canvas, &outline, transform, FillRule::NonZero, paint);
+}
+
+pub fn draw_text_transformed<'font, I, T, F>(
+    image: &I,
+    paint: T,
+    transform: &Transform2D,
+    anchor: TextAnchor,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    F: FontSource<'font>,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_transformed_mut(&mut out, paint, transform, anchor, scale, font, text);
+    out
+}
+
+pub fn draw_text_on_path_mut<'font, C, T, F>(
+    canvas: &mut C,
+    paint: T,
+    path: &Path,
+    style: &PathTextStyle,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) where
+    C: Canvas,
+    F: FontSource<'font>,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let outline = text_path_outline(scale, font, text, path, style);
+    fill_path_mut(canvas, &outline, FillRule::NonZero, paint);
+}
+
+pub fn draw_text_on_path<'font, I, T, F>(
+    image: &I,
+    paint: T,
+    path: &Path,
+    style: &PathTextStyle,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    F: FontSource<'font>,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_text_on_path_mut(&mut out, paint, path, style, scale, font, text);
+    out
+}
+
+pub fn draw_styled_text_mut<'font, C, T, F>(
+    canvas: &mut C,
+    paint: T,
+    transform: &Transform2D,
+    style: &TextStyle<C::Pixel>,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) where
+    C: Canvas,
+    F: FontSource<'font>,
+    T: Paint<C::Pixel>,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let v_metrics = match font.primary() {
+        Some(primary) => primary.v_metrics(scale),
+        None => return,
+    };
+    let advance_width = measure(scale, font, text);
+    let (dx, dy) = style
+        .anchor()
+        .offset(advance_width, v_metrics.ascent, v_metrics.descent);
+    let outline = text_outline(scale, font, text, Point::new(dx, dy));
+
+    if let Some(background) = style.background() {
+        let padding = background.padding;
+        let mut rect = Path::new();
+        rect.add_rect(
+            dx - padding,
+            dy - v_metrics.ascent - padding,
+            advance_width + 2.0 * padding,
+            v_metrics.ascent - v_metrics.descent + 2.0 * padding,
+        );
+        fill_path_transformed_mut(
+            canvas,
+            &rect,
+            transform,
+            FillRule::NonZero,
+            Solid(background.color),
+        );
+    }
+
+    let stroke = style.outline().map(|outline| {
+        (
+            StrokeStyle {
+                width: outline.width,
+                join: LineJoin::Round,
+                ..StrokeStyle::default()
+            },
+            outline.color,
+        )
+    });
+    let scale_factor = transform.max_scale();
+    let stroked = match &stroke {
+        Some((stroke_style, _)) if scale_factor > 0.0 && scale_factor.is_finite() => {
+            Some(outline.stroke_with_tolerance(stroke_style, FLATTEN_TOLERANCE / scale_factor))
+        }
+        _ => None,
+    };
+
+    if let Some(shadow) = style.shadow() {
+        let shapes: Vec<&Path> = [Some(&outline), stroked.as_ref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        draw_shadow(canvas, &shapes, transform, shadow);
+    }
+
+    if let (Some(stroked), Some((_, color))) = (&stroked, stroke) {
+        fill_path_transformed_mut(canvas, stroked, transform, FillRule::NonZero, Solid(color));
+    }
+
+    fill_path_transformed_mut(canvas, &outline, transform, FillRule::NonZero, paint);
+}
+
+pub fn draw_styled_text<'font, I, T, F>(
+    image: &I,
+    paint: T,
+    transform: &Transform2D,
+    style: &TextStyle<I::Pixel>,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    F: FontSource<'font>,
+    T: Paint<I::Pixel>,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_styled_text_mut(&mut out, paint, transform, style, scale, font, text);
+    out
+}
+
+fn anchor_offset<'font, F: FontSource<'font>>(
+    anchor: TextAnchor,
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> Option<(f32, f32)> {
+    let v_metrics = font.primary()?.v_metrics(scale);
+    Some(anchor.offset(
+        measure(scale, font, text),
+        v_metrics.ascent,
+        v_metrics.descent,
+    ))
+}
+
+fn draw_shadow<C>(
+    canvas: &mut C,
+    shapes: &[&Path],
+    transform: &Transform2D,
+    shadow: &TextShadow<C::Pixel>,
+) where
+    C: Canvas,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let transform = transform.then(&Transform2D::translate(shadow.offset_x, shadow.offset_y));
+    let shapes: Vec<Path> = shapes.iter().map(|s| s.transform(&transform)).collect();
+    let bounds = match shapes
+        .iter()
+        .filter_map(|s| s.bounds())
+        .reduce(|a, b| a.union(b))
+    {
+        Some(bounds) => bounds,
+        None => return,
+    };
+
+    let (width, height) = canvas.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let blur_radius = shadow.blur_radius.max(0.0).min(width.max(height) as f32);
+    let margin = ((1.5 * blur_radius).ceil() + 1.0).min((i32::MAX / 4) as f32) as i32;
+    let limit = Rect::at(-margin, -margin).of_size(
+        width.saturating_add(2 * margin as u32),
+        height.saturating_add(2 * margin as u32),
+    );
+    let expanded = Bounds::new(
+        Point::new(bounds.min.x - margin as f32, bounds.min.y - margin as f32),
+        Point::new(bounds.max.x + margin as f32, bounds.max.y + margin as f32),
+    );
+    let region = match expanded.to_rect_within(limit) {
+        Some(region) => region,
+        None => return,
+    };
+
+    let mut mask = Mask::new(region.width(), region.height());
+    let to_mask = Transform2D::translate(-region.left() as f32, -region.top() as f32);
+    for shape in &shapes {
+        fill_path_mut(
+            &mut mask,
+            &shape.transform(&to_mask),
+            FillRule::NonZero,
+            Luma([1.0f32]),
+        );
+    }
+    mask.blur(blur_radius);
+
+    let mut area = Path::new();
+    area.add_rect(
+        region.left() as f32,
+        region.top() as f32,
+        region.width() as f32,
+        region.height() as f32,
+    );
+    let mut masked = Masked::new(canvas, &mask).with_offset(region.left(), region.top());
+    fill_path_mut(&mut masked, &area, FillRule::NonZero, Solid(shadow.color));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::{test_font, HorizontalAnchor, VerticalAnchor};
+    use image::{GrayImage, Rgb, RgbImage};
+
+    fn ink_bounds(image: &GrayImage) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for (x, y, pixel) in image.enumerate_pixels() {
+            if pixel[0] > 0 {
+                bounds = Some(match bounds {
+                    Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                    None => (x, y, x, y),
+                });
+            }
+        }
+        bounds
+    }
+
+    #[test]
+    fn transformed_text_follows_the_transform() {
+        let font = test_font();
+        let scale = Scale::uniform(16.0);
+        let draw = |transform: &Transform2D| {
+            let mut image = GrayImage::new(80, 80);
+            draw_text_transformed_mut(
+                &mut image,
+                Luma([255u8]),
+                transform,
+                TextAnchor::default(),
+                scale,
+                &font,
+                "ab",
+            );
+            image
+        };
+
+        let plain = draw(&Transform2D::identity());
+        let moved = draw(&Transform2D::translate(20.0, 30.0));
+        for (x, y, pixel) in plain
+            .enumerate_pixels()
+            .filter(|(x, y, _)| *x < 60 && *y < 50)
+        {
+            let shifted = moved.get_pixel(x + 20, y + 30)[0];
+            assert!((pixel[0] as i32 - shifted as i32).abs() <= 1, "{} {}", x, y);
+        }
+
+        let (x0, y0, x1, y1) = ink_bounds(&plain).unwrap();
+        let (sx0, sy0, sx1, sy1) = ink_bounds(&draw(&Transform2D::scale(2.0, 2.0))).unwrap();
+        assert!((sx1 - sx0) + 1 >= 2 * (x1 - x0));
+        assert!((sy1 - sy0) + 1 >= 2 * (y1 - y0));
+    }
+
+    #[test]
+    fn transformed_text_is_anchored_before_transforming() {
+        let font = test_font();
+        let mut image = GrayImage::new(80, 40);
+        let anchor = TextAnchor::new(HorizontalAnchor::End, VerticalAnchor::Baseline);
+        let transform = Transform2D::translate(60.0, 30.0);
+        draw_text_transformed_mut(
+            &mut image,
+            Luma([255u8]),
+            &transform,
+            anchor,
+            Scale::uniform(16.0),
+            &font,
+            "ab",
+        );
+        let (_, y0, x1, y1) = ink_bounds(&image).unwrap();
+        assert!((58..=60).contains(&x1));
+        assert!(y0 < 30 && (29..=30).contains(&y1));
+    }
+
+    #[test]
+    fn transformed_text_without_fonts_draws_nothing() {
+        let mut image = GrayImage::new(20, 20);
+        draw_text_transformed_mut(
+            &mut image,
+            Luma([255u8]),
+            &Transform2D::identity(),
+            TextAnchor::default(),
+            Scale::uniform(16.0),
+            &FontCollection::new(),
+            "ab",
+        );
+        assert!(ink_bounds(&image).is_none());
+    }
+
+    #[test]
+    fn background_covers_the_text_box() {
+        let font = test_font();
+        let scale = Scale::uniform(16.0);
+        let style = TextStyle::new().with_background(2.0, Rgb([0, 0, 255]));
+        let image = draw_styled_text(
+            &RgbImage::new(60, 30),
+            Solid(Rgb([255, 255, 255])),
+            &Transform2D::translate(5.0, 5.0),
+            &style,
+            scale,
+            &font,
+            "ab",
+        );
+        let advance = measure(scale, &font, "ab");
+        let v_metrics = font.v_metrics(scale);
+        let height = v_metrics.ascent - v_metrics.descent;
+        assert_eq!(*image.get_pixel(4, 4), Rgb([0, 0, 255]));
+        assert_eq!(*image.get_pixel(2, 2), Rgb([0, 0, 0]));
+        assert_eq!(
+            *image.get_pixel((5.0 + advance) as u32 + 1, (5.0 + height) as u32),
+            Rgb([0, 0, 255])
+        );
+        assert_eq!(
+            *image.get_pixel((7.0 + advance) as u32 + 1, (7.0 + height) as u32 + 1),
+            Rgb([0, 0, 0])
+        );
+        assert!(image.pixels().any(|p| *p == Rgb([255, 255, 255])));
+    }
+
+    #[test]
+    fn outline_surrounds_the_glyphs() {
+        let font = test_font();
+        let scale = Scale::uniform(20.0);
+        let transform = Transform2D::translate(10.0, 10.0);
+        let draw = |style: &TextStyle<Rgb<u8>>| {
+            draw_styled_text(
+                &RgbImage::new(60, 40),
+                Solid(Rgb([255, 255, 255])),
+                &transform,
+                style,
+                scale,
+                &font,
+                "o",
+            )
+        };
+        let plain = draw(&TextStyle::new());
+        let outlined = draw(&TextStyle::new().with_outline(3.0, Rgb([255, 0, 0])));
+
+        let mut red = 0;
+        for (x, y, pixel) in outlined.enumerate_pixels() {
+            if *plain.get_pixel(x, y) == Rgb([255, 255, 255]) {
+                assert_eq!(*pixel, Rgb([255, 255, 255]));
+            }
+            if *pixel == Rgb([255, 0, 0]) {
+                red += 1;
+                assert_eq!(*plain.get_pixel(x, y), Rgb([0, 0, 0]));
+            }
+        }
+        assert!(red > 0);
+    }
+
+    #[test]
+    fn shadow_is_offset_and_blurred() {
+        let font = test_font();
+        let scale = Scale::uniform(20.0);
+        let draw = |style: &TextStyle<Luma<u8>>| {
+            draw_styled_text(
+                &GrayImage::new(60, 40),
+                Solid(Luma([0u8])),
+                &Transform2D::translate(5.0, 5.0),
+                style,
+                scale,
+                &font,
+                "I",
+            )
+        };
+        let sharp = draw(&TextStyle::new().with_shadow(20.0, 10.0, 0.0, Luma([255])));
+        let (x0, y0, x1, y1) = ink_bounds(&sharp).unwrap();
+        let glyph = ink_bounds(&draw(&TextStyle::new().with_shadow(
+            0.0,
+            0.0,
+            0.0,
+            Luma([255]),
+        )))
+        .unwrap();
+        assert_eq!((x0, y0), (glyph.0 + 20, glyph.1 + 10));
+        assert_eq!((x1, y1), (glyph.2 + 20, glyph.3 + 10));
+
+        let blurred = draw(&TextStyle::new().with_shadow(20.0, 10.0, 4.0, Luma([255])));
+        let (bx0, by0, bx1, by1) = ink_bounds(&blurred).unwrap();
+        assert!(bx0 < x0 && by0 < y0 && bx1 > x1 && by1 > y1);
+        assert!(blurred.get_pixel((x0 + x1) / 2, (y0 + y1) / 2)[0] < 255);
+
+        for radius in &[f32::INFINITY, f32::NAN, 1e30, -5.0] {
+            draw(&TextStyle::new().with_shadow(0.0, 0.0, *radius, Luma([255])));
+        }
+        draw(&TextStyle::new().with_shadow(f32::MAX, -f32::MAX, 2.0, Luma([255])));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bounds {
+    pub min: Point<f32>,
+    pub max: Point<f32>,
+}
+
+impl Bounds {
+    pub fn new(min: Point<f32>, max: Point<f32>) -> Bounds {
+        Bounds {
+            min: Point::new(min.x.min(max.x), min.y.min(max.y)),
+            max: Point::new(min.x.max(max.x), min.y.max(max.y)),
+        }
+    }
+
+    pub fn from_point(point: Point<f32>) -> Bounds {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn include(&mut self, point: Point<f32>) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+    }
+
+    pub fn union(&self, other: Bounds) -> Bounds {
+        let mut out = *self;
+        out.include(other.min);
+        out.include(other.max);
+        out
+    }
+
+    pub fn to_rect_within(&self, area: Rect) -> Option<Rect> {
+        let left = (self.min.x.floor() as i64).max(area.left() as i64);
+        let top = (self.min.y.floor() as i64).max(area.top() as i64);
+        let right = (self.max.x.ceil() as i64).min(area.right() as i64 + 1);
+        let bottom = (self.max.y.ceil() as i64).min(area.bottom() as i64 + 1);
+
+        if right <= left || bottom <= top {
+            return None;
+        }
+
+        Some(
+            Rect::at(left as i32, top as i32).of_size((right - left) as u32, (bottom - top) as u32),
+        )
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(rect: Rect) -> Bounds {
+        Bounds {
+            min: Point::new(rect.left() as f32, rect.top() as f32),
+            max: Point::new((rect.right() + 1) as f32, (rect.bottom() + 1) as f32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x0: f32, y0: f32, x1: f32, y1: f32) -> Bounds {
+        Bounds::new(Point::new(x0, y0), Point::new(x1, y1))
+    }
+
+    #[test]
+    fn rect_covers_fractional_bounds() {
+        let area = Rect::at(0, 0).of_size(10, 10);
+        assert_eq!(
+            bounds(1.5, 2.25, 4.5, 3.0).to_rect_within(area),
+            Some(Rect::at(1, 2).of_size(4, 1))
+        );
+    }
+
+    #[test]
+    fn rect_is_limited_to_the_area() {
+        let area = Rect::at(0, 0).of_size(10, 10);
+        assert_eq!(
+            bounds(-3e9, 0.0, 3e9, 5.0).to_rect_within(area),
+            Some(Rect::at(0, 0).of_size(10, 5))
+        );
+        assert_eq!(
+            bounds(
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+                f32::INFINITY,
+                f32::INFINITY
+            )
+            .to_rect_within(area),
+            Some(area)
+        );
+    }
+
+    #[test]
+    fn rect_outside_the_area_is_none() {
+        let area = Rect::at(0, 0).of_size(10, 10);
+        assert_eq!(bounds(20.0, 0.0, 30.0, 5.0).to_rect_within(area), None);
+        assert_eq!(bounds(2.0, 2.0, 2.0, 8.0).to_rect_within(area), None);
+        assert_eq!(bounds(-1e30, 0.0, -1e29, 5.0).to_rect_within(area), None);
+    }
+}
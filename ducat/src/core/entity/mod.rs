@@ -1,3 +1,5 @@
+pub mod bounds;
 pub mod definitions;
 pub mod point;
 pub mod rect;
+pub mod transform;
@@ -63,39 +63,6 @@ pub fn distance_sq<T: NumCast>(p: Point<T>, q: Point<T>) -> f64 {
     (p.x - q.x).powf(2.0) + (p.y - q.y).powf(2.0)
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub(crate) struct Rotation {
-    sin_theta: f64,
-    cos_theta: f64,
-}
-
-impl Rotation {
-    #[allow(dead_code)]
-    pub fn new(theta: f64) -> Rotation {
-        let (sin_theta, cos_theta) = theta.sin_cos();
-        Rotation {
-            sin_theta,
-            cos_theta,
-        }
-    }
-}
-
-impl Point<f64> {
-    #[allow(dead_code)]
-    pub(crate) fn rotate(&self, rotation: Rotation) -> Point<f64> {
-        let x = self.x * rotation.cos_theta + self.y * rotation.sin_theta;
-        let y = self.y * rotation.cos_theta - self.x * rotation.sin_theta;
-        Point::new(x, y)
-    }
-
-    #[allow(dead_code)]
-    pub(crate) fn invert_rotation(&self, rotation: Rotation) -> Point<f64> {
-        let x = self.x * rotation.cos_theta - self.y * rotation.sin_theta;
-        let y = self.y * rotation.cos_theta + self.x * rotation.sin_theta;
-        Point::new(x, y)
-    }
-}
-
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(crate) struct Line {
     a: f64,
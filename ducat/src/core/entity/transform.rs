@@ -0,0 +1,192 @@
+use crate::core::entity::bounds::Bounds;
+use crate::core::entity::point::Point;
+use crate::core::entity::rect::Rect;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform2D {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Transform2D {
+    pub fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Transform2D {
+        Transform2D { a, b, c, d, e, f }
+    }
+
+    pub fn identity() -> Transform2D {
+        Transform2D::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translate(tx: f32, ty: f32) -> Transform2D {
+        Transform2D::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Transform2D {
+        Transform2D::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    pub fn rotate(angle: f32) -> Transform2D {
+        let (sin, cos) = angle.sin_cos();
+        Transform2D::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    pub fn rotate_about(angle: f32, cx: f32, cy: f32) -> Transform2D {
+        Transform2D::translate(-cx, -cy)
+            .then(&Transform2D::rotate(angle))
+            .then(&Transform2D::translate(cx, cy))
+    }
+
+    pub fn skew(angle_x: f32, angle_y: f32) -> Transform2D {
+        Transform2D::new(1.0, angle_y.tan(), angle_x.tan(), 1.0, 0.0, 0.0)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Transform2D::identity()
+    }
+
+    pub fn then(&self, other: &Transform2D) -> Transform2D {
+        Transform2D::new(
+            other.a * self.a + other.c * self.b,
+            other.b * self.a + other.d * self.b,
+            other.a * self.c + other.c * self.d,
+            other.b * self.c + other.d * self.d,
+            other.a * self.e + other.c * self.f + other.e,
+            other.b * self.e + other.d * self.f + other.f,
+        )
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.a * self.d - self.b * self.c
+    }
+
+    pub fn invert(&self) -> Option<Transform2D> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Transform2D::new(
+            a,
+            b,
+            c,
+            d,
+            -(a * self.e + c * self.f),
+            -(b * self.e + d * self.f),
+        ))
+    }
+
+    pub(crate) fn max_scale(&self) -> f32 {
+        (self.a * self.a + self.b * self.b)
+            .max(self.c * self.c + self.d * self.d)
+            .sqrt()
+    }
+
+    pub fn apply(&self, p: Point<f32>) -> Point<f32> {
+        Point::new(
+            self.a * p.x + self.c * p.y + self.e,
+            self.b * p.x + self.d * p.y + self.f,
+        )
+    }
+
+    pub fn apply_bounds(&self, bounds: Bounds) -> Bounds {
+        let corners = [
+            bounds.min,
+            Point::new(bounds.max.x, bounds.min.y),
+            bounds.max,
+            Point::new(bounds.min.x, bounds.max.y),
+        ];
+        let mut out = Bounds::from_point(self.apply(corners[0]));
+        for &corner in &corners[1..] {
+            out.include(self.apply(corner));
+        }
+        out
+    }
+
+    pub fn apply_rect(&self, rect: Rect) -> Bounds {
+        self.apply_bounds(Bounds::from(rect))
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Transform2D {
+        Transform2D::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close_to(a: Point<f32>, b: Point<f32>) -> bool {
+        (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4
+    }
+
+    #[test]
+    fn then_applies_in_order() {
+        let p = Point::new(1.0, 2.0);
+        let scale_then_move =
+            Transform2D::scale(2.0, 3.0).then(&Transform2D::translate(10.0, 20.0));
+        assert_eq!(scale_then_move.apply(p), Point::new(12.0, 26.0));
+        let move_then_scale =
+            Transform2D::translate(10.0, 20.0).then(&Transform2D::scale(2.0, 3.0));
+        assert_eq!(move_then_scale.apply(p), Point::new(22.0, 66.0));
+        assert!(Transform2D::identity()
+            .then(&Transform2D::identity())
+            .is_identity());
+    }
+
+    #[test]
+    fn rotate_about_keeps_the_center() {
+        let rotate = Transform2D::rotate_about(std::f32::consts::FRAC_PI_2, 5.0, 5.0);
+        assert!(close_to(
+            rotate.apply(Point::new(5.0, 5.0)),
+            Point::new(5.0, 5.0)
+        ));
+        assert!(close_to(
+            rotate.apply(Point::new(6.0, 5.0)),
+            Point::new(5.0, 6.0)
+        ));
+    }
+
+    #[test]
+    fn invert_round_trips() {
+        let transforms = [
+            Transform2D::translate(3.0, -7.0),
+            Transform2D::scale(2.0, 0.5),
+            Transform2D::rotate_about(0.7, 10.0, 4.0),
+            Transform2D::skew(0.3, -0.2).then(&Transform2D::translate(1.0, 2.0)),
+        ];
+        let p = Point::new(4.0, -9.0);
+        for transform in &transforms {
+            let inverse = transform.invert().unwrap();
+            assert!(close_to(inverse.apply(transform.apply(p)), p));
+            assert!(close_to(transform.then(&inverse).apply(p), p));
+            assert!(close_to(inverse.then(transform).apply(p), p));
+        }
+    }
+
+    #[test]
+    fn singular_transforms_have_no_inverse() {
+        assert!(Transform2D::scale(0.0, 1.0).invert().is_none());
+        assert!(Transform2D::new(1.0, 2.0, 2.0, 4.0, 0.0, 0.0)
+            .invert()
+            .is_none());
+        assert!(Transform2D::scale(f32::INFINITY, 1.0).invert().is_none());
+    }
+
+    #[test]
+    fn apply_rect_bounds_every_corner() {
+        let rotate = Transform2D::rotate(std::f32::consts::FRAC_PI_4);
+        let bounds = rotate.apply_rect(Rect::at(0, 0).of_size(2, 2));
+        let half_diagonal = 2.0f32.sqrt();
+        assert!(close_to(bounds.min, Point::new(-half_diagonal, 0.0)));
+        assert!(close_to(
+            bounds.max,
+            Point::new(half_diagonal, 2.0 * half_diagonal)
+        ));
+    }
+}
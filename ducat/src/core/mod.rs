@@ -1,3 +1,7 @@
+pub mod context;
 pub mod drawing;
 pub mod entity;
 pub mod operation;
+pub mod paint;
+pub mod path;
+pub mod text;
@@ -0,0 +1,254 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::operation::premultiply::{lum, premultiply, unpremultiply};
+use conv::ValueInto;
+use image::Pixel;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    Clear,
+    Source,
+    Destination,
+    #[default]
+    SourceOver,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor,
+    Plus,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    fn porter_duff_factors(self, sa: f32, da: f32) -> Option<(f32, f32)> {
+        let factors = match self {
+            BlendMode::Clear => (0.0, 0.0),
+            BlendMode::Source => (1.0, 0.0),
+            BlendMode::Destination => (0.0, 1.0),
+            BlendMode::SourceOver => (1.0, 1.0 - sa),
+            BlendMode::DestinationOver => (1.0 - da, 1.0),
+            BlendMode::SourceIn => (da, 0.0),
+            BlendMode::DestinationIn => (0.0, sa),
+            BlendMode::SourceOut => (1.0 - da, 0.0),
+            BlendMode::DestinationOut => (0.0, 1.0 - sa),
+            BlendMode::SourceAtop => (da, 1.0 - sa),
+            BlendMode::DestinationAtop => (1.0 - da, sa),
+            BlendMode::Xor => (1.0 - da, 1.0 - sa),
+            BlendMode::Plus => (1.0, 1.0),
+            _ => return None,
+        };
+        Some(factors)
+    }
+
+    pub(crate) fn keeps_uncovered_destination(self) -> bool {
+        self.porter_duff_factors(0.0, 1.0)
+            .is_none_or(|(_, fb)| fb == 1.0)
+    }
+
+    fn blend(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        let separable =
+            |f: fn(f32, f32) -> f32| [f(cb[0], cs[0]), f(cb[1], cs[1]), f(cb[2], cs[2])];
+        match self {
+            BlendMode::Multiply => separable(|b, s| b * s),
+            BlendMode::Screen => separable(screen),
+            BlendMode::Overlay => separable(|b, s| hard_light(s, b)),
+            BlendMode::Darken => separable(f32::min),
+            BlendMode::Lighten => separable(f32::max),
+            BlendMode::ColorDodge => separable(|b, s| {
+                if b <= 0.0 {
+                    0.0
+                } else if s >= 1.0 {
+                    1.0
+                } else {
+                    (b / (1.0 - s)).min(1.0)
+                }
+            }),
+            BlendMode::ColorBurn => separable(|b, s| {
+                if b >= 1.0 {
+                    1.0
+                } else if s <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - b) / s).min(1.0)
+                }
+            }),
+            BlendMode::HardLight => separable(hard_light),
+            BlendMode::SoftLight => separable(|b, s| {
+                if s <= 0.5 {
+                    b - (1.0 - 2.0 * s) * b * (1.0 - b)
+                } else {
+                    let d = if b <= 0.25 {
+                        ((16.0 * b - 12.0) * b + 4.0) * b
+                    } else {
+                        b.sqrt()
+                    };
+                    b + (2.0 * s - 1.0) * (d - b)
+                }
+            }),
+            BlendMode::Difference => separable(|b, s| (b - s).abs()),
+            BlendMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+            BlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            BlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            BlendMode::Color => set_lum(cs, lum(cb)),
+            BlendMode::Luminosity => set_lum(cb, lum(cs)),
+            _ => cs,
+        }
+    }
+
+    fn apply(self, cs: [f32; 3], sa: f32, cb: [f32; 3], da: f32) -> ([f32; 3], f32) {
+        let mut out = [0.0; 3];
+        match self.porter_duff_factors(sa, da) {
+            Some((fa, fb)) => {
+                for i in 0..3 {
+                    out[i] = sa * fa * cs[i] + da * fb * cb[i];
+                }
+                (out, (sa * fa + da * fb).min(1.0))
+            }
+            None => {
+                let blended = self.blend(cb, cs);
+                for i in 0..3 {
+                    let mixed = (1.0 - da) * cs[i] + da * blended[i];
+                    out[i] = sa * mixed + da * cb[i] * (1.0 - sa);
+                }
+                (out, sa + da * (1.0 - sa))
+            }
+        }
+    }
+}
+
+pub fn composite<P: Pixel>(src: P, dst: P, mode: BlendMode, coverage: f32) -> P
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    unpremultiply(composite_premultiplied(
+        premultiply(&src),
+        premultiply(&dst),
+        mode,
+        coverage,
+    ))
+}
+
+pub fn composite_premultiplied(
+    src: [f32; 4],
+    dst: [f32; 4],
+    mode: BlendMode,
+    coverage: f32,
+) -> [f32; 4] {
+    let coverage = coverage.clamp(0.0, 1.0);
+    let (cs, sa) = straight(src);
+    let (cb, da) = straight(dst);
+    let (co, ao) = mode.apply(cs, sa, cb, da);
+    [
+        coverage * co[0] + (1.0 - coverage) * dst[0],
+        coverage * co[1] + (1.0 - coverage) * dst[1],
+        coverage * co[2] + (1.0 - coverage) * dst[2],
+        coverage * ao + (1.0 - coverage) * dst[3],
+    ]
+}
+
+fn straight(rgba: [f32; 4]) -> ([f32; 3], f32) {
+    let alpha = rgba[3];
+    if alpha > 0.0 {
+        ([rgba[0] / alpha, rgba[1] / alpha, rgba[2] / alpha], alpha)
+    } else {
+        ([0.0; 3], 0.0)
+    }
+}
+
+fn screen(b: f32, s: f32) -> f32 {
+    b + s - b * s
+}
+
+fn hard_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 {
+        b * 2.0 * s
+    } else {
+        screen(b, 2.0 * s - 1.0)
+    }
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut out = c;
+    for v in out.iter_mut() {
+        if n < 0.0 {
+            *v = l + (*v - l) * l / (l - n);
+        }
+        if x > 1.0 {
+            *v = l + (*v - l) * (1.0 - l) / (x - l);
+        }
+    }
+    out
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| c[a].total_cmp(&c[b]));
+    let (min, mid, max) = (order[0], order[1], order[2]);
+
+    let mut out = [0.0; 3];
+    if c[max] > c[min] {
+        out[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        out[max] = s;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn source_over_half_coverage() {
+        let out = composite(
+            Rgba([255u8, 255, 255, 255]),
+            Rgba([0u8, 0, 0, 255]),
+            BlendMode::SourceOver,
+            0.5,
+        );
+        assert_eq!(out, Rgba([128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn non_separable_modes_tolerate_nan() {
+        let src = [f32::NAN, 0.5, 0.25, 1.0];
+        let dst = [0.2, 0.4, 0.6, 1.0];
+        for &mode in &[
+            BlendMode::Hue,
+            BlendMode::Saturation,
+            BlendMode::Color,
+            BlendMode::Luminosity,
+        ] {
+            composite_premultiplied(src, dst, mode, 1.0);
+        }
+    }
+}
@@ -1,2 +1,6 @@
+pub mod composite;
 pub mod math;
 pub mod pixelops;
+pub mod premultiply;
+pub mod sample;
+pub mod srgb;
@@ -0,0 +1,101 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::operation::math::cast;
+use conv::ValueInto;
+use image::{Pixel, Primitive};
+
+pub fn premultiply<P: Pixel>(pixel: &P) -> [f32; 4]
+where
+    P::Subpixel: ValueInto<f32>,
+{
+    let (color, alpha) = unpack(pixel);
+    [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha]
+}
+
+pub fn unpremultiply<P: Pixel>(rgba: [f32; 4]) -> P
+where
+    P::Subpixel: Clamp<f32>,
+{
+    let alpha = rgba[3];
+    let color = if !P::COLOR_TYPE.has_alpha() {
+        [rgba[0], rgba[1], rgba[2]]
+    } else if alpha > 0.0 {
+        [rgba[0] / alpha, rgba[1] / alpha, rgba[2] / alpha]
+    } else {
+        [0.0; 3]
+    };
+    pack(color, alpha)
+}
+
+pub(crate) fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+pub(crate) fn channel_max<S: Primitive>() -> f32 {
+    match S::max_value().to_f32() {
+        Some(max) if max < f32::MAX => max,
+        _ => 1.0,
+    }
+}
+
+fn is_bgr<P: Pixel>() -> bool {
+    P::COLOR_MODEL.starts_with("BGR")
+}
+
+pub(crate) fn unpack<P: Pixel>(pixel: &P) -> ([f32; 3], f32)
+where
+    P::Subpixel: ValueInto<f32>,
+{
+    let max = channel_max::<P::Subpixel>();
+    let channels = pixel.channels();
+    let has_alpha = P::COLOR_TYPE.has_alpha();
+    let color_count = channels.len() - has_alpha as usize;
+
+    let alpha = if has_alpha {
+        cast(channels[channels.len() - 1]) / max
+    } else {
+        1.0
+    };
+    let mut color = [0.0; 3];
+    if color_count < 3 {
+        color = [cast(channels[0]) / max; 3];
+    } else {
+        for (c, &v) in color.iter_mut().zip(channels) {
+            *c = cast(v) / max;
+        }
+        if is_bgr::<P>() {
+            color.swap(0, 2);
+        }
+    }
+    (color, alpha)
+}
+
+pub(crate) fn pack<P: Pixel>(mut color: [f32; 3], alpha: f32) -> P
+where
+    P::Subpixel: Clamp<f32>,
+{
+    let zeros = [<P::Subpixel as num::Zero>::zero(); 4];
+    let mut pixel = *P::from_slice(&zeros[..P::CHANNEL_COUNT as usize]);
+    let max = channel_max::<P::Subpixel>();
+    let to_channel = |v: f32| {
+        let v = v.clamp(0.0, 1.0) * max;
+        Clamp::clamp(if max > 1.0 { v.round() } else { v })
+    };
+
+    let has_alpha = P::COLOR_TYPE.has_alpha();
+    let channels = pixel.channels_mut();
+    let color_count = channels.len() - has_alpha as usize;
+    if has_alpha {
+        channels[color_count] = to_channel(alpha);
+    }
+    if color_count < 3 {
+        channels[0] = to_channel(lum(color));
+    } else {
+        if is_bgr::<P>() {
+            color.swap(0, 2);
+        }
+        for (c, &v) in channels.iter_mut().zip(color.iter()) {
+            *c = to_channel(v);
+        }
+    }
+    pixel
+}
@@ -0,0 +1,212 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::operation::math::cast;
+use crate::core::operation::premultiply::channel_max;
+use conv::ValueInto;
+use image::{GenericImageView, Pixel};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    #[default]
+    Nearest,
+    Bilinear,
+    Bicubic,
+    Lanczos3,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Wrap {
+    #[default]
+    Clamp,
+    Repeat,
+}
+
+pub fn sample<I>(
+    image: &I,
+    x: f32,
+    y: f32,
+    interpolation: Interpolation,
+    wrap_x: Wrap,
+    wrap_y: Wrap,
+) -> I::Pixel
+where
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (x, y) = (limit(x), limit(y));
+    let fetch = |x: i64, y: i64| {
+        let x = wrap(x, image.width(), wrap_x);
+        let y = wrap(y, image.height(), wrap_y);
+        image.get_pixel(x, y)
+    };
+
+    match interpolation {
+        Interpolation::Nearest => fetch(x.floor() as i64, y.floor() as i64),
+        Interpolation::Bilinear => {
+            let (fx, fy) = (x - 0.5, y - 0.5);
+            let (x0, y0) = (fx.floor(), fy.floor());
+            let (tx, ty) = (fx - x0, fy - y0);
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            weighted_pixel_sum(&[
+                (fetch(x0, y0), (1.0 - tx) * (1.0 - ty)),
+                (fetch(x0 + 1, y0), tx * (1.0 - ty)),
+                (fetch(x0, y0 + 1), (1.0 - tx) * ty),
+                (fetch(x0 + 1, y0 + 1), tx * ty),
+            ])
+        }
+        Interpolation::Bicubic => convolve(fetch, x, y, 2, catmull_rom),
+        Interpolation::Lanczos3 => convolve(fetch, x, y, 3, lanczos3),
+    }
+}
+
+const COORDINATE_LIMIT: f32 = 1_099_511_627_776.0;
+
+fn limit(v: f32) -> f32 {
+    if v.is_nan() {
+        0.0
+    } else {
+        v.clamp(-COORDINATE_LIMIT, COORDINATE_LIMIT)
+    }
+}
+
+fn convolve<P, F>(fetch: F, x: f32, y: f32, radius: i64, kernel: fn(f32) -> f32) -> P
+where
+    P: Pixel,
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+    F: Fn(i64, i64) -> P,
+{
+    let (fx, fy) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (fx.floor() as i64, fy.floor() as i64);
+    let taps = |f: f32, origin: i64| -> Vec<(i64, f32)> {
+        let taps: Vec<(i64, f32)> = (origin - radius + 1..=origin + radius)
+            .map(|i| (i, kernel(f - i as f32)))
+            .collect();
+        let total: f32 = taps.iter().map(|&(_, w)| w).sum();
+        taps.into_iter().map(|(i, w)| (i, w / total)).collect()
+    };
+
+    let (xs, ys) = (taps(fx, x0), taps(fy, y0));
+    let mut samples = Vec::with_capacity(xs.len() * ys.len());
+    for &(sy, wy) in &ys {
+        for &(sx, wx) in &xs {
+            samples.push((fetch(sx, sy), wx * wy));
+        }
+    }
+    weighted_pixel_sum(&samples)
+}
+
+fn catmull_rom(t: f32) -> f32 {
+    let t = t.abs();
+    if t < 1.0 {
+        (1.5 * t - 2.5) * t * t + 1.0
+    } else if t < 2.0 {
+        ((-0.5 * t + 2.5) * t - 4.0) * t + 2.0
+    } else {
+        0.0
+    }
+}
+
+fn lanczos3(t: f32) -> f32 {
+    let sinc = |v: f32| {
+        if v == 0.0 {
+            1.0
+        } else {
+            let v = v * std::f32::consts::PI;
+            v.sin() / v
+        }
+    };
+    if t.abs() < 3.0 {
+        sinc(t) * sinc(t / 3.0)
+    } else {
+        0.0
+    }
+}
+
+fn wrap(v: i64, size: u32, mode: Wrap) -> u32 {
+    let size = size as i64;
+    match mode {
+        Wrap::Clamp => v.clamp(0, size - 1) as u32,
+        Wrap::Repeat => v.rem_euclid(size) as u32,
+    }
+}
+
+fn weighted_pixel_sum<P: Pixel>(samples: &[(P, f32)]) -> P
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let max = channel_max::<P::Subpixel>();
+    let alpha_index = if P::COLOR_TYPE.has_alpha() {
+        P::CHANNEL_COUNT as usize - 1
+    } else {
+        usize::MAX
+    };
+
+    let mut sums = [0f32; 4];
+    for (pixel, weight) in samples {
+        if *weight == 0.0 {
+            continue;
+        }
+        let channels = pixel.channels();
+        let alpha = channels.get(alpha_index).map_or(1.0, |&a| cast(a) / max);
+        for (i, (sum, &c)) in sums.iter_mut().zip(channels).enumerate() {
+            let value: f32 = cast(c);
+            *sum += if i == alpha_index {
+                value * weight
+            } else {
+                value * alpha * weight
+            };
+        }
+    }
+
+    let alpha = sums.get(alpha_index).map_or(1.0, |&a| a / max);
+    let mut out = samples[0].0;
+    for (i, (c, &sum)) in out.channels_mut().iter_mut().zip(sums.iter()).enumerate() {
+        let value = if i == alpha_index {
+            sum
+        } else if alpha > 0.0 {
+            sum / alpha
+        } else {
+            0.0
+        };
+        let value = value.clamp(0.0, max);
+        *c = Clamp::clamp(if max > 1.0 { value.round() } else { value });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn non_finite_coordinates_are_clamped() {
+        let image = GrayImage::from_fn(2, 2, |x, y| Luma([(x * 100 + y * 50) as u8]));
+        let interpolations = [
+            Interpolation::Nearest,
+            Interpolation::Bilinear,
+            Interpolation::Bicubic,
+            Interpolation::Lanczos3,
+        ];
+        for &interpolation in &interpolations {
+            for &wrap in &[Wrap::Clamp, Wrap::Repeat] {
+                for &(x, y) in &[
+                    (f32::INFINITY, 0.5),
+                    (f32::NEG_INFINITY, f32::INFINITY),
+                    (f32::NAN, 1.5),
+                    (1e30, -1e30),
+                ] {
+                    sample(&image, x, y, interpolation, wrap, wrap);
+                }
+            }
+            let far = sample(
+                &image,
+                f32::INFINITY,
+                0.5,
+                interpolation,
+                Wrap::Clamp,
+                Wrap::Clamp,
+            );
+            assert_eq!(far, Luma([100]));
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use image::Pixel;
+use std::sync::OnceLock;
+
+pub trait SrgbSubpixel: Copy {
+    fn to_linear(self) -> f32;
+
+    fn from_linear(value: f32) -> Self;
+
+    fn to_unit(self) -> f32;
+
+    fn from_unit(value: f32) -> Self;
+}
+
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn decode_table(size: usize) -> Vec<f32> {
+    let max = (size - 1) as f32;
+    (0..size).map(|i| srgb_to_linear(i as f32 / max)).collect()
+}
+
+fn encode_with(table: &[f32], value: f32) -> usize {
+    let i = table.partition_point(|&v| v < value);
+    if i == 0 {
+        0
+    } else if i == table.len() || value - table[i - 1] < table[i] - value {
+        i - 1
+    } else {
+        i
+    }
+}
+
+fn u8_table() -> &'static [f32] {
+    static TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+    TABLE.get_or_init(|| decode_table(1 << 8))
+}
+
+fn u16_table() -> &'static [f32] {
+    static TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+    TABLE.get_or_init(|| decode_table(1 << 16))
+}
+
+impl SrgbSubpixel for u8 {
+    fn to_linear(self) -> f32 {
+        u8_table()[self as usize]
+    }
+
+    fn from_linear(value: f32) -> u8 {
+        encode_with(u8_table(), value) as u8
+    }
+
+    fn to_unit(self) -> f32 {
+        self as f32 / u8::MAX as f32
+    }
+
+    fn from_unit(value: f32) -> u8 {
+        (value.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8
+    }
+}
+
+impl SrgbSubpixel for u16 {
+    fn to_linear(self) -> f32 {
+        u16_table()[self as usize]
+    }
+
+    fn from_linear(value: f32) -> u16 {
+        encode_with(u16_table(), value) as u16
+    }
+
+    fn to_unit(self) -> f32 {
+        self as f32 / u16::MAX as f32
+    }
+
+    fn from_unit(value: f32) -> u16 {
+        (value.clamp(0.0, 1.0) * u16::MAX as f32).round() as u16
+    }
+}
+
+impl SrgbSubpixel for f32 {
+    fn to_linear(self) -> f32 {
+        srgb_to_linear(self)
+    }
+
+    fn from_linear(value: f32) -> f32 {
+        linear_to_srgb(value.clamp(0.0, 1.0))
+    }
+
+    fn to_unit(self) -> f32 {
+        self
+    }
+
+    fn from_unit(value: f32) -> f32 {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+fn decode<P: Pixel>(pixel: &P) -> ([f32; 4], f32)
+where
+    P::Subpixel: SrgbSubpixel,
+{
+    let channels = pixel.channels();
+    let color_count = color_channel_count::<P>();
+    let alpha = if color_count < channels.len() {
+        channels[color_count].to_unit()
+    } else {
+        1.0
+    };
+    let mut color = [0.0; 4];
+    for (c, &v) in color.iter_mut().zip(&channels[..color_count]) {
+        *c = v.to_linear() * alpha;
+    }
+    (color, alpha)
+}
+
+fn encode<P: Pixel>(mut pixel: P, color: [f32; 4], alpha: f32) -> P
+where
+    P::Subpixel: SrgbSubpixel,
+{
+    let color_count = color_channel_count::<P>();
+    let channels = pixel.channels_mut();
+    if color_count < channels.len() {
+        channels[color_count] = SrgbSubpixel::from_unit(alpha);
+    }
+    for (c, &v) in channels[..color_count].iter_mut().zip(color.iter()) {
+        let straight = if alpha > 0.0 { v / alpha } else { 0.0 };
+        *c = SrgbSubpixel::from_linear(straight);
+    }
+    pixel
+}
+
+fn color_channel_count<P: Pixel>() -> usize {
+    P::CHANNEL_COUNT as usize - P::COLOR_TYPE.has_alpha() as usize
+}
+
+pub fn linear_weighted_sum<P: Pixel>(left: P, right: P, left_weight: f32, right_weight: f32) -> P
+where
+    P::Subpixel: SrgbSubpixel,
+{
+    let (l, la) = decode(&left);
+    let (r, ra) = decode(&right);
+    let mut color = [0.0; 4];
+    for i in 0..4 {
+        color[i] = l[i] * left_weight + r[i] * right_weight;
+    }
+    encode(left, color, la * left_weight + ra * right_weight)
+}
+
+pub fn linear_interpolate<P: Pixel>(left: P, right: P, left_weight: f32) -> P
+where
+    P::Subpixel: SrgbSubpixel,
+{
+    linear_weighted_sum(left, right, left_weight, 1.0 - left_weight)
+}
+
+pub fn linear_source_over<P: Pixel>(src: P, dst: P, coverage: f32) -> P
+where
+    P::Subpixel: SrgbSubpixel,
+{
+    let coverage = coverage.clamp(0.0, 1.0);
+    let (s, sa) = decode(&src);
+    let (d, da) = decode(&dst);
+    let sa = sa * coverage;
+    let mut color = [0.0; 4];
+    for i in 0..4 {
+        color[i] = s[i] * coverage + d[i] * (1.0 - sa);
+    }
+    encode(dst, color, sa + da * (1.0 - sa))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::operation::pixelops::interpolate;
+    use image::{Luma, Rgba};
+
+    #[test]
+    fn u8_table_round_trips() {
+        for v in 0..=u8::MAX {
+            assert_eq!(u8::from_linear(v.to_linear()), v);
+        }
+    }
+
+    #[test]
+    fn u16_table_round_trips() {
+        for v in 0..=u16::MAX {
+            assert_eq!(u16::from_linear(v.to_linear()), v);
+        }
+    }
+
+    #[test]
+    fn transfer_functions_are_inverse() {
+        for i in 0..=1000 {
+            let v = i as f32 / 1000.0;
+            assert!((linear_to_srgb(srgb_to_linear(v)) - v).abs() < 1e-5);
+        }
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!((srgb_to_linear(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn half_coverage_is_lighter_in_linear_light() {
+        let white = Luma([255u8]);
+        let black = Luma([0u8]);
+        assert_eq!(interpolate(white, black, 0.5), Luma([127]));
+        assert_eq!(linear_interpolate(white, black, 0.5), Luma([188]));
+        assert_eq!(
+            linear_source_over(Rgba([255u8, 255, 255, 255]), Rgba([0, 0, 0, 255]), 0.5),
+            Rgba([188, 188, 188, 255])
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::entity::point::Point;
+use crate::core::operation::premultiply::{premultiply, unpremultiply};
+use crate::core::paint::Paint;
+use conv::ValueInto;
+use image::Pixel;
+use std::f32::consts::TAU;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum SpreadMode {
+    #[default]
+    Pad,
+    Repeat,
+    Reflect,
+}
+
+impl SpreadMode {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            SpreadMode::Pad => t.clamp(0.0, 1.0),
+            SpreadMode::Repeat => t.rem_euclid(1.0),
+            SpreadMode::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorStop<P> {
+    pub offset: f32,
+    pub color: P,
+}
+
+impl<P> ColorStop<P> {
+    pub fn new(offset: f32, color: P) -> ColorStop<P> {
+        ColorStop { offset, color }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorRamp<P> {
+    stops: Vec<ColorStop<P>>,
+    spread: SpreadMode,
+}
+
+impl<P: Pixel> ColorRamp<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    pub fn new(mut stops: Vec<ColorStop<P>>) -> ColorRamp<P> {
+        assert!(!stops.is_empty(), "a color ramp needs at least one stop");
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        ColorRamp {
+            stops,
+            spread: SpreadMode::Pad,
+        }
+    }
+
+    pub fn with_spread(mut self, spread: SpreadMode) -> ColorRamp<P> {
+        self.spread = spread;
+        self
+    }
+
+    pub fn stops(&self) -> &[ColorStop<P>] {
+        &self.stops
+    }
+
+    pub fn spread(&self) -> SpreadMode {
+        self.spread
+    }
+
+    pub fn color_at(&self, t: f32) -> P {
+        let t = self.spread.apply(t);
+        let first = &self.stops[0];
+        let last = &self.stops[self.stops.len() - 1];
+        if t <= first.offset {
+            return first.color;
+        }
+        if t >= last.offset {
+            return last.color;
+        }
+
+        let i = self.stops.partition_point(|stop| stop.offset <= t);
+        let (a, b) = (&self.stops[i - 1], &self.stops[i]);
+        let w = (t - a.offset) / (b.offset - a.offset);
+        let (a, b) = (premultiply(&a.color), premultiply(&b.color));
+        let mut mixed = [0.0; 4];
+        for (m, (a, b)) in mixed.iter_mut().zip(a.iter().zip(b.iter())) {
+            *m = a + (b - a) * w;
+        }
+        unpremultiply(mixed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient<P> {
+    start: Point<f32>,
+    end: Point<f32>,
+    ramp: ColorRamp<P>,
+}
+
+impl<P> LinearGradient<P> {
+    pub fn new(start: Point<f32>, end: Point<f32>, ramp: ColorRamp<P>) -> LinearGradient<P> {
+        LinearGradient { start, end, ramp }
+    }
+}
+
+impl<P: Pixel> Paint<P> for LinearGradient<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fn color_at(&self, x: f32, y: f32) -> P {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        let length_sq = dx * dx + dy * dy;
+        let t = if length_sq > 0.0 {
+            ((x - self.start.x) * dx + (y - self.start.y) * dy) / length_sq
+        } else {
+            0.0
+        };
+        self.ramp.color_at(t)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient<P> {
+    center: Point<f32>,
+    radius: f32,
+    focal: Point<f32>,
+    ramp: ColorRamp<P>,
+}
+
+impl<P> RadialGradient<P> {
+    pub fn new(center: Point<f32>, radius: f32, ramp: ColorRamp<P>) -> RadialGradient<P> {
+        RadialGradient {
+            center,
+            radius,
+            focal: center,
+            ramp,
+        }
+    }
+
+    pub fn with_focal(mut self, focal: Point<f32>) -> RadialGradient<P> {
+        let (ex, ey) = (focal.x - self.center.x, focal.y - self.center.y);
+        let distance = (ex * ex + ey * ey).sqrt();
+        let max_distance = self.radius * 0.999;
+        self.focal = if distance > max_distance {
+            let scale = max_distance / distance;
+            Point::new(self.center.x + ex * scale, self.center.y + ey * scale)
+        } else {
+            focal
+        };
+        self
+    }
+}
+
+impl<P: Pixel> Paint<P> for RadialGradient<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fn color_at(&self, x: f32, y: f32) -> P {
+        let (dx, dy) = (x - self.focal.x, y - self.focal.y);
+        let (ex, ey) = (self.focal.x - self.center.x, self.focal.y - self.center.y);
+
+        let a = dx * dx + dy * dy;
+        if a == 0.0 || self.radius <= 0.0 {
+            return self.ramp.color_at(0.0);
+        }
+        let b = ex * dx + ey * dy;
+        let c = ex * ex + ey * ey - self.radius * self.radius;
+        let s = (-b + (b * b - a * c).max(0.0).sqrt()) / a;
+
+        self.ramp.color_at(1.0 / s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicGradient<P> {
+    center: Point<f32>,
+    start_angle: f32,
+    ramp: ColorRamp<P>,
+}
+
+impl<P> ConicGradient<P> {
+    pub fn new(center: Point<f32>, start_angle: f32, ramp: ColorRamp<P>) -> ConicGradient<P> {
+        ConicGradient {
+            center,
+            start_angle,
+            ramp,
+        }
+    }
+}
+
+impl<P: Pixel> Paint<P> for ConicGradient<P>
+where
+    P::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fn color_at(&self, x: f32, y: f32) -> P {
+        let angle = (y - self.center.y).atan2(x - self.center.x);
+        self.ramp
+            .color_at(((angle - self.start_angle) / TAU).rem_euclid(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn transparent_stop_keeps_color() {
+        let ramp = ColorRamp::new(vec![
+            ColorStop::new(0.0, Rgba([255u8, 0, 0, 255])),
+            ColorStop::new(1.0, Rgba([0u8, 0, 0, 0])),
+        ]);
+        assert_eq!(ramp.color_at(0.5), Rgba([255, 0, 0, 128]));
+        assert_eq!(ramp.color_at(0.75), Rgba([255, 0, 0, 64]));
+    }
+
+    #[test]
+    fn stops_are_sorted_and_nan_tolerant() {
+        let ramp = ColorRamp::new(vec![
+            ColorStop::new(1.0, Rgba([255u8, 255, 255, 255])),
+            ColorStop::new(f32::NAN, Rgba([0u8, 0, 255, 255])),
+            ColorStop::new(0.0, Rgba([0u8, 0, 0, 255])),
+        ]);
+        assert_eq!(ramp.stops()[0].offset, 0.0);
+        assert_eq!(ramp.stops()[1].offset, 1.0);
+        assert_eq!(ramp.color_at(0.5), Rgba([128, 128, 128, 255]));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::core::entity::point::Point;
+use crate::core::entity::transform::Transform2D;
+use image::{Bgr, Bgra, Luma, LumaA, Primitive, Rgb, Rgba};
+use std::rc::Rc;
+
+mod gradient;
+pub use self::gradient::{
+    ColorRamp, ColorStop, ConicGradient, LinearGradient, RadialGradient, SpreadMode,
+};
+
+mod pattern;
+pub use self::pattern::{ImagePattern, PatternRepeat};
+
+pub trait Paint<P> {
+    fn color_at(&self, x: f32, y: f32) -> P;
+
+    fn coverage_at(&self, _x: f32, _y: f32) -> f32 {
+        1.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Solid<P>(pub P);
+
+impl<P: Copy> Paint<P> for Solid<P> {
+    fn color_at(&self, _x: f32, _y: f32) -> P {
+        self.0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TransformedPaint<T> {
+    paint: T,
+    inverse: Option<Transform2D>,
+}
+
+impl<T> TransformedPaint<T> {
+    pub fn new(paint: T, transform: Transform2D) -> TransformedPaint<T> {
+        TransformedPaint {
+            paint,
+            inverse: transform.invert(),
+        }
+    }
+}
+
+impl<P, T: Paint<P>> Paint<P> for TransformedPaint<T> {
+    fn color_at(&self, x: f32, y: f32) -> P {
+        let p = match self.inverse {
+            Some(inverse) => inverse.apply(Point::new(x, y)),
+            None => Point::new(x, y),
+        };
+        self.paint.color_at(p.x, p.y)
+    }
+
+    fn coverage_at(&self, x: f32, y: f32) -> f32 {
+        match self.inverse {
+            Some(inverse) => {
+                let p = inverse.apply(Point::new(x, y));
+                self.paint.coverage_at(p.x, p.y)
+            }
+            None => 0.0,
+        }
+    }
+}
+
+impl<P, T: Paint<P> + ?Sized> Paint<P> for &T {
+    fn color_at(&self, x: f32, y: f32) -> P {
+        (**self).color_at(x, y)
+    }
+
+    fn coverage_at(&self, x: f32, y: f32) -> f32 {
+        (**self).coverage_at(x, y)
+    }
+}
+
+impl<P, T: Paint<P> + ?Sized> Paint<P> for Rc<T> {
+    fn color_at(&self, x: f32, y: f32) -> P {
+        (**self).color_at(x, y)
+    }
+
+    fn coverage_at(&self, x: f32, y: f32) -> f32 {
+        (**self).coverage_at(x, y)
+    }
+}
+
+macro_rules! impl_solid_paint {
+    ( $($pixel:ident),* ) => {
+        $(
+            impl<T: Primitive> Paint<$pixel<T>> for $pixel<T> {
+                fn color_at(&self, _x: f32, _y: f32) -> $pixel<T> {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+impl_solid_paint!(Luma, LumaA, Rgb, Rgba, Bgr, Bgra);
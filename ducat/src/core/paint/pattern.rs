@@ -0,0 +1,184 @@
+use crate::core::entity::definitions::Clamp;
+use crate::core::entity::point::Point;
+use crate::core::entity::transform::Transform2D;
+use crate::core::operation::sample::{sample, Interpolation, Wrap};
+use crate::core::paint::Paint;
+use conv::ValueInto;
+use image::{GenericImageView, Pixel};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PatternRepeat {
+    #[default]
+    Repeat,
+    RepeatX,
+    RepeatY,
+    NoRepeat,
+}
+
+impl PatternRepeat {
+    fn wrap(self) -> (Wrap, Wrap) {
+        match self {
+            PatternRepeat::Repeat => (Wrap::Repeat, Wrap::Repeat),
+            PatternRepeat::RepeatX => (Wrap::Repeat, Wrap::Clamp),
+            PatternRepeat::RepeatY => (Wrap::Clamp, Wrap::Repeat),
+            PatternRepeat::NoRepeat => (Wrap::Clamp, Wrap::Clamp),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImagePattern<I> {
+    image: I,
+    repeat: PatternRepeat,
+    interpolation: Interpolation,
+    transform: Transform2D,
+    inverse: Option<Transform2D>,
+}
+
+impl<I: GenericImageView> ImagePattern<I> {
+    pub fn new(image: I) -> ImagePattern<I> {
+        assert!(
+            image.width() > 0 && image.height() > 0,
+            "a pattern image must not be empty"
+        );
+        ImagePattern {
+            image,
+            repeat: PatternRepeat::Repeat,
+            interpolation: Interpolation::Nearest,
+            transform: Transform2D::identity(),
+            inverse: Some(Transform2D::identity()),
+        }
+    }
+
+    pub fn with_repeat(mut self, repeat: PatternRepeat) -> ImagePattern<I> {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> ImagePattern<I> {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Transform2D) -> ImagePattern<I> {
+        self.transform = transform;
+        self.inverse = transform.invert();
+        self
+    }
+
+    pub fn image(&self) -> &I {
+        &self.image
+    }
+
+    pub fn repeat(&self) -> PatternRepeat {
+        self.repeat
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    fn axis_coverage(&self, v: f32, size: u32, wrap: Wrap) -> f32 {
+        let size = size as f32;
+        match (wrap, self.interpolation) {
+            (Wrap::Repeat, _) => 1.0,
+            (Wrap::Clamp, Interpolation::Nearest) => {
+                if v >= 0.0 && v < size {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            (Wrap::Clamp, _) => (v + 0.5).clamp(0.0, 1.0) * (size + 0.5 - v).clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl<I> Paint<I::Pixel> for ImagePattern<I>
+where
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    fn color_at(&self, x: f32, y: f32) -> I::Pixel {
+        let p = match self.inverse {
+            Some(inverse) => inverse.apply(Point::new(x, y)),
+            None => Point::new(0.0, 0.0),
+        };
+        let (wrap_x, wrap_y) = self.repeat.wrap();
+        sample(&self.image, p.x, p.y, self.interpolation, wrap_x, wrap_y)
+    }
+
+    fn coverage_at(&self, x: f32, y: f32) -> f32 {
+        let p = match self.inverse {
+            Some(inverse) => inverse.apply(Point::new(x, y)),
+            None => return 0.0,
+        };
+        let (wrap_x, wrap_y) = self.repeat.wrap();
+        self.axis_coverage(p.x, self.image.width(), wrap_x)
+            * self.axis_coverage(p.y, self.image.height(), wrap_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    fn checker() -> GrayImage {
+        GrayImage::from_fn(2, 2, |x, y| Luma([if (x + y) % 2 == 0 { 0 } else { 200 }]))
+    }
+
+    #[test]
+    fn repeat_wraps_in_both_directions() {
+        let pattern = ImagePattern::new(checker());
+        assert_eq!(pattern.color_at(0.5, 0.5), Luma([0]));
+        assert_eq!(pattern.color_at(2.5, 0.5), Luma([0]));
+        assert_eq!(pattern.color_at(-0.5, 0.5), Luma([200]));
+        assert_eq!(pattern.color_at(0.5, -2.5), Luma([200]));
+        assert_eq!(pattern.coverage_at(-100.0, 100.0), 1.0);
+    }
+
+    #[test]
+    fn single_axis_repeat_limits_coverage() {
+        let x = ImagePattern::new(checker()).with_repeat(PatternRepeat::RepeatX);
+        assert_eq!(x.coverage_at(10.5, 1.5), 1.0);
+        assert_eq!(x.coverage_at(0.5, 2.5), 0.0);
+        assert_eq!(x.color_at(3.5, 1.5), Luma([0]));
+
+        let y = ImagePattern::new(checker()).with_repeat(PatternRepeat::RepeatY);
+        assert_eq!(y.coverage_at(1.5, -10.5), 1.0);
+        assert_eq!(y.coverage_at(-0.5, 0.5), 0.0);
+
+        let none = ImagePattern::new(checker()).with_repeat(PatternRepeat::NoRepeat);
+        assert_eq!(none.coverage_at(1.5, 1.5), 1.0);
+        assert_eq!(none.coverage_at(2.0, 1.5), 0.0);
+    }
+
+    #[test]
+    fn bilinear_sampling_blends_neighbours() {
+        let image = GrayImage::from_fn(2, 1, |x, _| Luma([(x * 200) as u8]));
+        let pattern = ImagePattern::new(image)
+            .with_repeat(PatternRepeat::NoRepeat)
+            .with_interpolation(Interpolation::Bilinear);
+        assert_eq!(pattern.color_at(0.5, 0.5), Luma([0]));
+        assert_eq!(pattern.color_at(1.0, 0.5), Luma([100]));
+        assert_eq!(pattern.color_at(1.5, 0.5), Luma([200]));
+        assert_eq!(pattern.coverage_at(1.0, 0.5), 1.0);
+        assert_eq!(pattern.coverage_at(-0.25, 0.5), 0.25);
+    }
+
+    #[test]
+    fn transform_maps_canvas_to_pattern_space() {
+        let pattern = ImagePattern::new(checker()).with_transform(Transform2D::scale(2.0, 2.0));
+        assert_eq!(pattern.color_at(1.0, 1.0), Luma([0]));
+        assert_eq!(pattern.color_at(3.0, 1.0), Luma([200]));
+        assert_eq!(pattern.color_at(5.0, 1.0), Luma([0]));
+
+        let singular = ImagePattern::new(checker()).with_transform(Transform2D::scale(0.0, 1.0));
+        assert_eq!(singular.coverage_at(1.0, 1.0), 0.0);
+    }
+}
@@ -0,0 +1,428 @@
+use crate::core::entity::bounds::Bounds;
+use crate::core::entity::point::Point;
+use crate::core::entity::transform::Transform2D;
+use crate::core::path::segment::{cubic_extrema, cubic_point, quad_extrema, quad_point};
+use crate::core::path::PathSegment;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+    start: Option<Point<f32>>,
+    current: Option<Point<f32>>,
+}
+
+impl Path {
+    pub fn new() -> Path {
+        Path::default()
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn current_point(&self) -> Option<Point<f32>> {
+        self.current
+    }
+
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        let p = Point::new(x, y);
+        match self.segments.last_mut() {
+            Some(PathSegment::MoveTo(last)) => *last = p,
+            _ => self.segments.push(PathSegment::MoveTo(p)),
+        }
+        self.start = Some(p);
+        self.current = Some(p);
+        self
+    }
+
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        if self.ensure_sub_path(x, y) {
+            self.push(PathSegment::LineTo(Point::new(x, y)));
+        }
+        self
+    }
+
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) -> &mut Self {
+        self.ensure_sub_path(cx, cy);
+        self.push(PathSegment::QuadTo(Point::new(cx, cy), Point::new(x, y)));
+        self
+    }
+
+    pub fn cubic_to(
+        &mut self,
+        c1x: f32,
+        c1y: f32,
+        c2x: f32,
+        c2y: f32,
+        x: f32,
+        y: f32,
+    ) -> &mut Self {
+        self.ensure_sub_path(c1x, c1y);
+        self.push(PathSegment::CubicTo(
+            Point::new(c1x, c1y),
+            Point::new(c2x, c2y),
+            Point::new(x, y),
+        ));
+        self
+    }
+
+    pub fn arc(
+        &mut self,
+        cx: f32,
+        cy: f32,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        anticlockwise: bool,
+    ) -> &mut Self {
+        let mut sweep = end_angle - start_angle;
+        if anticlockwise {
+            sweep = -sweep;
+        }
+        sweep = if sweep >= TAU {
+            TAU
+        } else {
+            sweep.rem_euclid(TAU)
+        };
+        if anticlockwise {
+            sweep = -sweep;
+        }
+
+        let (sin, cos) = start_angle.sin_cos();
+        let (x, y) = (cx + radius * cos, cy + radius * sin);
+        if self.current.is_some() {
+            self.line_to(x, y);
+        } else {
+            self.move_to(x, y);
+        }
+
+        self.append_arc(Point::new(cx, cy), radius, radius, start_angle, sweep);
+        self
+    }
+
+    pub fn arc_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, radius: f32) -> &mut Self {
+        let p0 = match self.current {
+            Some(p) => p,
+            None => return self.move_to(x1, y1),
+        };
+
+        let (v0x, v0y) = (p0.x - x1, p0.y - y1);
+        let (v2x, v2y) = (x2 - x1, y2 - y1);
+        let l0 = (v0x * v0x + v0y * v0y).sqrt();
+        let l2 = (v2x * v2x + v2y * v2y).sqrt();
+        if radius <= 0.0 || l0 < f32::EPSILON || l2 < f32::EPSILON {
+            return self.line_to(x1, y1);
+        }
+
+        let (u0x, u0y) = (v0x / l0, v0y / l0);
+        let (u2x, u2y) = (v2x / l2, v2y / l2);
+        let cross = u0x * u2y - u0y * u2x;
+        if cross.abs() < 1e-6 {
+            return self.line_to(x1, y1);
+        }
+
+        let half_angle = (u0x * u2x + u0y * u2y).clamp(-1.0, 1.0).acos() / 2.0;
+        let tangent_distance = radius / half_angle.tan();
+        let center_distance = radius / half_angle.sin();
+
+        let (bx, by) = (u0x + u2x, u0y + u2y);
+        let bl = (bx * bx + by * by).sqrt();
+        let center = Point::new(
+            x1 + bx / bl * center_distance,
+            y1 + by / bl * center_distance,
+        );
+
+        let t0 = Point::new(x1 + u0x * tangent_distance, y1 + u0y * tangent_distance);
+        let t2 = Point::new(x1 + u2x * tangent_distance, y1 + u2y * tangent_distance);
+        self.line_to(t0.x, t0.y);
+
+        let start_angle = (t0.y - center.y).atan2(t0.x - center.x);
+        let end_angle = (t2.y - center.y).atan2(t2.x - center.x);
+        let mut sweep = end_angle - start_angle;
+        if cross > 0.0 {
+            while sweep > 0.0 {
+                sweep -= TAU;
+            }
+        } else {
+            while sweep < 0.0 {
+                sweep += TAU;
+            }
+        }
+
+        self.append_arc(center, radius, radius, start_angle, sweep);
+        self
+    }
+
+    pub fn close(&mut self) -> &mut Self {
+        match self.segments.last() {
+            None | Some(PathSegment::Close) => {}
+            Some(_) => self.segments.push(PathSegment::Close),
+        }
+        self.current = self.start;
+        self
+    }
+
+    pub fn add_rect(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.move_to(x, y)
+            .line_to(x + width, y)
+            .line_to(x + width, y + height)
+            .line_to(x, y + height)
+            .close()
+    }
+
+    pub fn add_ellipse(&mut self, cx: f32, cy: f32, rx: f32, ry: f32) -> &mut Self {
+        self.move_to(cx + rx, cy);
+        self.append_arc(Point::new(cx, cy), rx, ry, 0.0, TAU);
+        self.close()
+    }
+
+    pub fn add_circle(&mut self, cx: f32, cy: f32, radius: f32) -> &mut Self {
+        self.add_ellipse(cx, cy, radius, radius)
+    }
+
+    pub fn add_polygon(&mut self, points: &[Point<f32>]) -> &mut Self {
+        if let Some((first, rest)) = points.split_first() {
+            self.move_to(first.x, first.y);
+            for p in rest {
+                self.line_to(p.x, p.y);
+            }
+            self.close();
+        }
+        self
+    }
+
+    pub fn sub_paths(&self) -> SubPaths<'_> {
+        SubPaths {
+            segments: &self.segments,
+        }
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut bounds: Option<Bounds> = None;
+        let mut include = |p: Point<f32>| match bounds.as_mut() {
+            Some(b) => b.include(p),
+            None => bounds = Some(Bounds::from_point(p)),
+        };
+
+        let mut last = Point::new(0.0, 0.0);
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::MoveTo(p) | PathSegment::LineTo(p) => include(p),
+                PathSegment::QuadTo(c, p) => {
+                    for t in quad_extrema(last, c, p) {
+                        include(quad_point(last, c, p, t));
+                    }
+                    include(p);
+                }
+                PathSegment::CubicTo(c1, c2, p) => {
+                    for t in cubic_extrema(last, c1, c2, p) {
+                        include(cubic_point(last, c1, c2, p, t));
+                    }
+                    include(p);
+                }
+                PathSegment::Close => {}
+            }
+            if let Some(p) = segment.end_point() {
+                last = p;
+            }
+        }
+
+        bounds
+    }
+
+    pub fn transform(&self, transform: &Transform2D) -> Path {
+        let apply = |p: Point<f32>| transform.apply(p);
+        Path {
+            segments: self
+                .segments
+                .iter()
+                .map(|segment| match *segment {
+                    PathSegment::MoveTo(p) => PathSegment::MoveTo(apply(p)),
+                    PathSegment::LineTo(p) => PathSegment::LineTo(apply(p)),
+                    PathSegment::QuadTo(c, p) => PathSegment::QuadTo(apply(c), apply(p)),
+                    PathSegment::CubicTo(c1, c2, p) => {
+                        PathSegment::CubicTo(apply(c1), apply(c2), apply(p))
+                    }
+                    PathSegment::Close => PathSegment::Close,
+                })
+                .collect(),
+            start: self.start.map(apply),
+            current: self.current.map(apply),
+        }
+    }
+
+    fn ensure_sub_path(&mut self, x: f32, y: f32) -> bool {
+        match (self.current, self.segments.last()) {
+            (None, _) => {
+                self.move_to(x, y);
+                false
+            }
+            (Some(start), Some(PathSegment::Close)) => {
+                self.segments.push(PathSegment::MoveTo(start));
+                true
+            }
+            _ => true,
+        }
+    }
+
+    fn push(&mut self, segment: PathSegment) {
+        self.current = segment.end_point();
+        self.segments.push(segment);
+    }
+
+    fn append_arc(&mut self, center: Point<f32>, rx: f32, ry: f32, start_angle: f32, sweep: f32) {
+        if sweep == 0.0 {
+            return;
+        }
+
+        let n = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as u32;
+        let step = sweep / n as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+        let point_at = |angle: f32| {
+            let (sin, cos) = angle.sin_cos();
+            (
+                Point::new(center.x + rx * cos, center.y + ry * sin),
+                Point::new(-rx * sin, ry * cos),
+            )
+        };
+
+        for i in 0..n {
+            let a0 = start_angle + step * i as f32;
+            let (p0, d0) = point_at(a0);
+            let (p3, d3) = point_at(a0 + step);
+            self.push(PathSegment::CubicTo(
+                Point::new(p0.x + k * d0.x, p0.y + k * d0.y),
+                Point::new(p3.x - k * d3.x, p3.y - k * d3.y),
+                p3,
+            ));
+        }
+    }
+}
+
+pub struct SubPaths<'a> {
+    segments: &'a [PathSegment],
+}
+
+impl<'a> Iterator for SubPaths<'a> {
+    type Item = &'a [PathSegment];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.segments.is_empty() {
+            return None;
+        }
+
+        let end = self.segments[1..]
+            .iter()
+            .position(|s| matches!(s, PathSegment::MoveTo(_)))
+            .map_or(self.segments.len(), |i| i + 1);
+        let (head, tail) = self.segments.split_at(end);
+        self.segments = tail;
+        Some(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close_to(a: Point<f32>, b: Point<f32>) -> bool {
+        (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3
+    }
+
+    #[test]
+    fn line_without_move_starts_a_sub_path() {
+        let mut path = Path::new();
+        path.line_to(1.0, 2.0).line_to(3.0, 4.0);
+        assert_eq!(
+            path.segments(),
+            &[
+                PathSegment::MoveTo(Point::new(1.0, 2.0)),
+                PathSegment::LineTo(Point::new(3.0, 4.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn drawing_after_close_restarts_at_the_sub_path_start() {
+        let mut path = Path::new();
+        path.move_to(1.0, 1.0)
+            .line_to(5.0, 1.0)
+            .close()
+            .line_to(5.0, 5.0);
+        assert_eq!(path.sub_paths().count(), 2);
+        assert_eq!(
+            &path.segments()[3..],
+            &[
+                PathSegment::MoveTo(Point::new(1.0, 1.0)),
+                PathSegment::LineTo(Point::new(5.0, 5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn arc_to_rounds_the_corner() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).arc_to(10.0, 0.0, 10.0, 10.0, 2.0);
+        let segments = path.segments();
+        assert!(matches!(segments[1], PathSegment::LineTo(p) if close_to(p, Point::new(8.0, 0.0))));
+        assert!(segments[2..]
+            .iter()
+            .all(|s| matches!(s, PathSegment::CubicTo(..))));
+        assert!(close_to(
+            path.current_point().unwrap(),
+            Point::new(10.0, 2.0)
+        ));
+
+        let center = Point::new(8.0, 2.0);
+        for segment in &segments[2..] {
+            let p = segment.end_point().unwrap();
+            let distance = ((p.x - center.x).powi(2) + (p.y - center.y).powi(2)).sqrt();
+            assert!((distance - 2.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn degenerate_arc_to_draws_a_line() {
+        let mut path = Path::new();
+        path.arc_to(4.0, 4.0, 8.0, 0.0, 2.0);
+        assert_eq!(
+            path.segments(),
+            &[PathSegment::MoveTo(Point::new(4.0, 4.0))]
+        );
+
+        path.arc_to(8.0, 8.0, 12.0, 12.0, 2.0);
+        assert_eq!(
+            path.segments()[1],
+            PathSegment::LineTo(Point::new(8.0, 8.0))
+        );
+
+        path.arc_to(12.0, 8.0, 12.0, 0.0, 0.0);
+        assert_eq!(
+            path.segments()[2],
+            PathSegment::LineTo(Point::new(12.0, 8.0))
+        );
+    }
+
+    #[test]
+    fn bounds_include_curve_extrema() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).quad_to(5.0, 10.0, 10.0, 0.0);
+        let bounds = path.bounds().unwrap();
+        assert_eq!(bounds.min, Point::new(0.0, 0.0));
+        assert_eq!(bounds.max, Point::new(10.0, 5.0));
+
+        let mut circle = Path::new();
+        circle.add_circle(10.0, 10.0, 5.0);
+        let bounds = circle.bounds().unwrap();
+        assert!(close_to(bounds.min, Point::new(5.0, 5.0)));
+        assert!(close_to(bounds.max, Point::new(15.0, 15.0)));
+
+        assert!(Path::new().bounds().is_none());
+    }
+}
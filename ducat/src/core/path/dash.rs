@@ -0,0 +1,146 @@
+use crate::core::entity::point::Point;
+use crate::core::path::segment::lerp;
+use crate::core::path::Polyline;
+
+pub(crate) fn dash_polyline(
+    polyline: &Polyline,
+    dash_array: &[f32],
+    dash_offset: f32,
+) -> Vec<Polyline> {
+    let mut pattern = dash_array.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dash_array);
+    }
+
+    let total: f32 = pattern.iter().sum();
+    if pattern.iter().any(|d| !d.is_finite() || *d < 0.0) || total <= 0.0 || !total.is_finite() {
+        return vec![polyline.clone()];
+    }
+
+    let mut index = 0;
+    let mut offset = dash_offset.rem_euclid(total);
+    while offset >= pattern[index] {
+        offset -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    let mut remaining = pattern[index] - offset;
+
+    let mut points = polyline.points.clone();
+    if polyline.closed {
+        points.push(points[0]);
+    }
+
+    let mut dashes = Vec::new();
+    let mut current: Vec<Point<f32>> = Vec::new();
+    if index % 2 == 0 {
+        current.push(points[0]);
+    }
+
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let length = ((b.x - a.x).powi(2) + (b.y - a.y).powi(2)).sqrt();
+        let mut position = 0.0;
+
+        while length - position > remaining {
+            position += remaining;
+            current.push(lerp(a, b, position / length));
+            if index % 2 == 0 {
+                dashes.push(Polyline {
+                    points: std::mem::take(&mut current),
+                    closed: false,
+                });
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+
+        remaining -= length - position;
+        if index % 2 == 0 {
+            current.push(b);
+        }
+    }
+
+    if index % 2 == 0 && current.len() > 1 {
+        dashes.push(Polyline {
+            points: current,
+            closed: false,
+        });
+    }
+
+    dashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(length: f32) -> Polyline {
+        Polyline {
+            points: vec![Point::new(0.0, 0.0), Point::new(length, 0.0)],
+            closed: false,
+        }
+    }
+
+    fn spans(dashes: &[Polyline]) -> Vec<(f32, f32)> {
+        dashes
+            .iter()
+            .map(|dash| (dash.points[0].x, dash.points[dash.points.len() - 1].x))
+            .collect()
+    }
+
+    #[test]
+    fn even_pattern() {
+        let dashes = dash_polyline(&line(20.0), &[4.0, 4.0], 0.0);
+        assert_eq!(spans(&dashes), vec![(0.0, 4.0), (8.0, 12.0), (16.0, 20.0)]);
+    }
+
+    #[test]
+    fn offset_shifts_pattern() {
+        let dashes = dash_polyline(&line(20.0), &[4.0, 4.0], 6.0);
+        assert_eq!(spans(&dashes), vec![(2.0, 6.0), (10.0, 14.0), (18.0, 20.0)]);
+        let dashes = dash_polyline(&line(20.0), &[4.0, 4.0], 2.0);
+        assert_eq!(spans(&dashes), vec![(0.0, 2.0), (6.0, 10.0), (14.0, 18.0)]);
+    }
+
+    #[test]
+    fn odd_pattern_is_repeated() {
+        let dashes = dash_polyline(&line(12.0), &[3.0], 0.0);
+        assert_eq!(spans(&dashes), vec![(0.0, 3.0), (6.0, 9.0)]);
+    }
+
+    #[test]
+    fn dashes_follow_arc_length_around_corners() {
+        let polyline = Polyline {
+            points: vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+            ],
+            closed: false,
+        };
+        let dashes = dash_polyline(&polyline, &[4.0, 4.0], 0.0);
+        assert_eq!(dashes.len(), 3);
+        assert_eq!(
+            dashes[1].points,
+            vec![
+                Point::new(8.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 2.0)
+            ]
+        );
+        assert_eq!(
+            dashes[2].points,
+            vec![Point::new(10.0, 6.0), Point::new(10.0, 10.0)]
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_returns_input() {
+        let polyline = line(10.0);
+        assert_eq!(
+            dash_polyline(&polyline, &[0.0, 0.0], 0.0),
+            vec![polyline.clone()]
+        );
+        assert_eq!(dash_polyline(&polyline, &[-1.0, 2.0], 0.0), vec![polyline]);
+    }
+}
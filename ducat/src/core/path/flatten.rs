@@ -0,0 +1,129 @@
+use crate::core::entity::point::Point;
+use crate::core::path::segment::{cubic_point, cubic_segments, quad_point, quad_segments};
+use crate::core::path::{Path, PathSegment};
+
+pub(crate) const FLATTEN_TOLERANCE: f32 = 0.05;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point<f32>>,
+    pub closed: bool,
+}
+
+impl Polyline {
+    pub fn length(&self) -> f32 {
+        let mut length: f32 = self.points.windows(2).map(|w| distance(w[0], w[1])).sum();
+        if self.closed {
+            if let (Some(&first), Some(&last)) = (self.points.first(), self.points.last()) {
+                length += distance(last, first);
+            }
+        }
+        length
+    }
+}
+
+fn distance(a: Point<f32>, b: Point<f32>) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+impl Path {
+    pub fn flatten(&self, tolerance: f32) -> Vec<Polyline> {
+        assert!(tolerance > 0.0, "tolerance must be strictly positive");
+
+        let mut polylines = Vec::new();
+
+        for sub_path in self.sub_paths() {
+            let mut points: Vec<Point<f32>> = Vec::new();
+            let mut closed = false;
+
+            for segment in sub_path {
+                let last = points.last().copied().unwrap_or(Point::new(0.0, 0.0));
+                match *segment {
+                    PathSegment::MoveTo(p) | PathSegment::LineTo(p) => points.push(p),
+                    PathSegment::QuadTo(c, p) => {
+                        let n = quad_segments(last, c, p, tolerance);
+                        for i in 1..=n {
+                            points.push(quad_point(last, c, p, i as f32 / n as f32));
+                        }
+                    }
+                    PathSegment::CubicTo(c1, c2, p) => {
+                        let n = cubic_segments(last, c1, c2, p, tolerance);
+                        for i in 1..=n {
+                            points.push(cubic_point(last, c1, c2, p, i as f32 / n as f32));
+                        }
+                    }
+                    PathSegment::Close => closed = true,
+                }
+            }
+
+            if closed && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            if points.len() > 1 {
+                polylines.push(Polyline { points, closed });
+            }
+        }
+
+        polylines
+    }
+
+    pub fn length(&self, tolerance: f32) -> f32 {
+        self.flatten(tolerance).iter().map(Polyline::length).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_segments_flatten_exactly() {
+        let mut path = Path::new();
+        path.add_rect(1.0, 2.0, 3.0, 4.0);
+        path.move_to(0.0, 0.0).line_to(5.0, 5.0);
+        assert_eq!(
+            path.flatten(0.1),
+            vec![
+                Polyline {
+                    points: vec![
+                        Point::new(1.0, 2.0),
+                        Point::new(4.0, 2.0),
+                        Point::new(4.0, 6.0),
+                        Point::new(1.0, 6.0)
+                    ],
+                    closed: true,
+                },
+                Polyline {
+                    points: vec![Point::new(0.0, 0.0), Point::new(5.0, 5.0)],
+                    closed: false,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn curves_flatten_within_tolerance() {
+        let tolerance = 0.1;
+        let mut path = Path::new();
+        path.add_circle(0.0, 0.0, 20.0);
+        let polylines = path.flatten(tolerance);
+        assert_eq!(polylines.len(), 1);
+        assert!(polylines[0].closed);
+
+        let points = &polylines[0].points;
+        assert!(points.len() > 8);
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            let middle = Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+            let r = middle.x.hypot(middle.y);
+            assert!(20.0 - r <= tolerance + 0.01, "chord sags {}", 20.0 - r);
+        }
+    }
+
+    #[test]
+    fn lone_move_is_dropped() {
+        let mut path = Path::new();
+        path.move_to(1.0, 1.0);
+        assert!(path.flatten(0.1).is_empty());
+    }
+}
@@ -0,0 +1,14 @@
+mod builder;
+pub use self::builder::{Path, SubPaths};
+
+mod dash;
+
+mod flatten;
+pub use self::flatten::Polyline;
+pub(crate) use self::flatten::FLATTEN_TOLERANCE;
+
+mod segment;
+pub use self::segment::PathSegment;
+
+mod stroke;
+pub use self::stroke::{LineCap, LineJoin, StrokeStyle};
@@ -0,0 +1,166 @@
+use crate::core::entity::point::Point;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PathSegment {
+    MoveTo(Point<f32>),
+    LineTo(Point<f32>),
+    QuadTo(Point<f32>, Point<f32>),
+    CubicTo(Point<f32>, Point<f32>, Point<f32>),
+    Close,
+}
+
+impl PathSegment {
+    pub fn end_point(&self) -> Option<Point<f32>> {
+        match *self {
+            PathSegment::MoveTo(p) | PathSegment::LineTo(p) => Some(p),
+            PathSegment::QuadTo(_, p) | PathSegment::CubicTo(_, _, p) => Some(p),
+            PathSegment::Close => None,
+        }
+    }
+}
+
+pub(crate) fn lerp(a: Point<f32>, b: Point<f32>, t: f32) -> Point<f32> {
+    Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+pub(crate) fn quad_point(p0: Point<f32>, p1: Point<f32>, p2: Point<f32>, t: f32) -> Point<f32> {
+    lerp(lerp(p0, p1, t), lerp(p1, p2, t), t)
+}
+
+pub(crate) fn cubic_point(
+    p0: Point<f32>,
+    p1: Point<f32>,
+    p2: Point<f32>,
+    p3: Point<f32>,
+    t: f32,
+) -> Point<f32> {
+    let mt = 1.0 - t;
+    let a = mt * mt * mt;
+    let b = 3.0 * mt * mt * t;
+    let c = 3.0 * mt * t * t;
+    let d = t * t * t;
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+        a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+    )
+}
+
+pub(crate) fn quad_segments(p0: Point<f32>, p1: Point<f32>, p2: Point<f32>, tolerance: f32) -> u32 {
+    let dd = second_difference(p0, p1, p2);
+    segment_count((dd / (4.0 * tolerance)).sqrt())
+}
+
+pub(crate) fn cubic_segments(
+    p0: Point<f32>,
+    p1: Point<f32>,
+    p2: Point<f32>,
+    p3: Point<f32>,
+    tolerance: f32,
+) -> u32 {
+    let dd = second_difference(p0, p1, p2).max(second_difference(p1, p2, p3));
+    segment_count((3.0 * dd / (4.0 * tolerance)).sqrt())
+}
+
+fn second_difference(p0: Point<f32>, p1: Point<f32>, p2: Point<f32>) -> f32 {
+    let x = p0.x - 2.0 * p1.x + p2.x;
+    let y = p0.y - 2.0 * p1.y + p2.y;
+    (x * x + y * y).sqrt()
+}
+
+fn segment_count(n: f32) -> u32 {
+    if n.is_finite() {
+        (n.ceil() as u32).clamp(1, 1000)
+    } else {
+        1
+    }
+}
+
+pub(crate) fn quad_extrema(p0: Point<f32>, p1: Point<f32>, p2: Point<f32>) -> Vec<f32> {
+    let solve = |a: f32, b: f32, c: f32| {
+        let denominator = a - 2.0 * b + c;
+        if denominator.abs() > f32::EPSILON {
+            Some((a - b) / denominator)
+        } else {
+            None
+        }
+    };
+
+    [solve(p0.x, p1.x, p2.x), solve(p0.y, p1.y, p2.y)]
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|t| *t > 0.0 && *t < 1.0)
+        .collect()
+}
+
+pub(crate) fn cubic_extrema(
+    p0: Point<f32>,
+    p1: Point<f32>,
+    p2: Point<f32>,
+    p3: Point<f32>,
+) -> Vec<f32> {
+    let mut roots = Vec::with_capacity(4);
+
+    for &(a, b, c, d) in &[(p0.x, p1.x, p2.x, p3.x), (p0.y, p1.y, p2.y, p3.y)] {
+        let qa = -a + 3.0 * b - 3.0 * c + d;
+        let qb = 2.0 * (a - 2.0 * b + c);
+        let qc = b - a;
+
+        if qa.abs() < f32::EPSILON {
+            if qb.abs() > f32::EPSILON {
+                roots.push(-qc / qb);
+            }
+            continue;
+        }
+
+        let discriminant = qb * qb - 4.0 * qa * qc;
+        if discriminant >= 0.0 {
+            let sqrt = discriminant.sqrt();
+            roots.push((-qb + sqrt) / (2.0 * qa));
+            roots.push((-qb - sqrt) / (2.0 * qa));
+        }
+    }
+
+    roots.retain(|t| *t > 0.0 && *t < 1.0);
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quad_extrema_find_the_turning_point() {
+        let (p0, p1, p2) = (
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 10.0),
+            Point::new(10.0, 0.0),
+        );
+        assert_eq!(quad_extrema(p0, p1, p2), vec![0.5]);
+        assert_eq!(quad_point(p0, p1, p2, 0.5), Point::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn cubic_extrema_stay_inside_the_segment() {
+        let (p0, p1, p2, p3) = (
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 10.0),
+            Point::new(10.0, 10.0),
+            Point::new(10.0, 0.0),
+        );
+        assert_eq!(cubic_extrema(p0, p1, p2, p3), vec![0.5]);
+        assert_eq!(cubic_point(p0, p1, p2, p3, 0.5), Point::new(5.0, 7.5));
+        let line = cubic_extrema(p0, Point::new(1.0, 1.0), Point::new(2.0, 2.0), p3);
+        assert!(line.iter().all(|t| *t > 0.0 && *t < 1.0));
+    }
+
+    #[test]
+    fn segment_counts_are_bounded() {
+        let (a, b) = (Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+        assert_eq!(quad_segments(a, Point::new(5.0, 0.0), b, 0.1), 1);
+        assert!(quad_segments(a, Point::new(5.0, 10.0), b, 0.1) > 1);
+        let far = Point::new(f32::INFINITY, 0.0);
+        assert_eq!(quad_segments(a, far, b, 0.1), 1);
+        assert_eq!(quad_segments(a, Point::new(5.0, 1e6), b, 1e-3), 1000);
+    }
+}
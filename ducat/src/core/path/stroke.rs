@@ -0,0 +1,375 @@
+use crate::core::entity::point::Point;
+use crate::core::path::dash::dash_polyline;
+use crate::core::path::{Path, Polyline, FLATTEN_TOLERANCE};
+use std::f32::consts::{PI, TAU};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f32,
+    pub dash_array: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> StrokeStyle {
+        StrokeStyle {
+            width,
+            ..StrokeStyle::default()
+        }
+    }
+
+    pub fn with_dash(mut self, dash_array: &[f32], dash_offset: f32) -> StrokeStyle {
+        self.dash_array = dash_array.to_vec();
+        self.dash_offset = dash_offset;
+        self
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> StrokeStyle {
+        StrokeStyle {
+            width: 1.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 10.0,
+            dash_array: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+impl Path {
+    pub fn stroke(&self, style: &StrokeStyle) -> Path {
+        self.stroke_with_tolerance(style, FLATTEN_TOLERANCE)
+    }
+
+    pub(crate) fn stroke_with_tolerance(&self, style: &StrokeStyle, tolerance: f32) -> Path {
+        let mut stroker = Stroker::new(style, tolerance);
+        for polyline in self.flatten(tolerance) {
+            if style.dash_array.is_empty() {
+                stroker.add_polyline(&polyline);
+            } else {
+                for dash in dash_polyline(&polyline, &style.dash_array, style.dash_offset) {
+                    stroker.add_polyline(&dash);
+                }
+            }
+        }
+        stroker.outline
+    }
+}
+
+struct Stroker<'a> {
+    style: &'a StrokeStyle,
+    half_width: f32,
+    tolerance: f32,
+    outline: Path,
+}
+
+impl<'a> Stroker<'a> {
+    fn new(style: &'a StrokeStyle, tolerance: f32) -> Stroker<'a> {
+        Stroker {
+            style,
+            half_width: style.width / 2.0,
+            tolerance,
+            outline: Path::new(),
+        }
+    }
+
+    fn add_polyline(&mut self, polyline: &Polyline) {
+        if self.half_width <= 0.0 {
+            return;
+        }
+
+        let mut points: Vec<Point<f32>> = Vec::with_capacity(polyline.points.len());
+        for &p in &polyline.points {
+            if points.last().is_none_or(|&q| length(p - q) > 1e-4) {
+                points.push(p);
+            }
+        }
+        if polyline.closed
+            && points.len() > 2
+            && length(points[0] - points[points.len() - 1]) <= 1e-4
+        {
+            points.pop();
+        }
+
+        if points.len() == 1 {
+            self.add_dot(points[0]);
+            return;
+        }
+
+        let closed = polyline.closed && points.len() > 2;
+        let segment_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+
+        for i in 0..segment_count {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+            let n = self.normal(a, b);
+            self.add_piece(vec![a + n, b + n, b - n, a - n]);
+        }
+
+        let joints = if closed {
+            0..points.len()
+        } else {
+            1..points.len() - 1
+        };
+        for i in joints {
+            let previous = points[(i + points.len() - 1) % points.len()];
+            let next = points[(i + 1) % points.len()];
+            self.add_join(previous, points[i], next);
+        }
+
+        if !closed {
+            self.add_cap(points[1], points[0]);
+            self.add_cap(points[points.len() - 2], points[points.len() - 1]);
+        }
+    }
+
+    fn add_dot(&mut self, center: Point<f32>) {
+        let hw = self.half_width;
+        match self.style.cap {
+            LineCap::Butt => {}
+            LineCap::Square => self.add_piece(vec![
+                Point::new(center.x - hw, center.y - hw),
+                Point::new(center.x + hw, center.y - hw),
+                Point::new(center.x + hw, center.y + hw),
+                Point::new(center.x - hw, center.y + hw),
+            ]),
+            LineCap::Round => {
+                let mut piece = Vec::new();
+                self.push_arc(&mut piece, center, 0.0, TAU);
+                self.add_piece(piece);
+            }
+        }
+    }
+
+    fn add_cap(&mut self, from: Point<f32>, end: Point<f32>) {
+        let n = self.normal(from, end);
+        match self.style.cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                let d = Point::new(-n.y, n.x);
+                self.add_piece(vec![end + n, end + n - d, end - n - d, end - n]);
+            }
+            LineCap::Round => {
+                let mut piece = vec![end];
+                self.push_arc(&mut piece, end, n.y.atan2(n.x), -PI);
+                self.add_piece(piece);
+            }
+        }
+    }
+
+    fn add_join(&mut self, previous: Point<f32>, p: Point<f32>, next: Point<f32>) {
+        let d0 = unit(p - previous);
+        let d1 = unit(next - p);
+        let cross = d0.x * d1.y - d0.y * d1.x;
+        let dot = d0.x * d1.x + d0.y * d1.y;
+
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            return;
+        }
+
+        let (side, turn) = if cross.abs() < 1e-6 {
+            (1.0, -PI)
+        } else {
+            (-cross.signum(), cross.atan2(dot))
+        };
+        let hw = self.half_width * side;
+        let o0 = p + Point::new(-d0.y * hw, d0.x * hw);
+        let o1 = p + Point::new(-d1.y * hw, d1.x * hw);
+
+        match self.style.join {
+            LineJoin::Bevel => self.add_piece(vec![p, o0, o1]),
+            LineJoin::Miter => {
+                let ratio = 1.0 / (turn / 2.0).cos();
+                if ratio.abs() <= self.style.miter_limit {
+                    let bisector = unit((o0 - p) + (o1 - p));
+                    let reach = self.half_width * ratio.abs();
+                    let miter = Point::new(p.x + bisector.x * reach, p.y + bisector.y * reach);
+                    self.add_piece(vec![p, o0, miter, o1]);
+                } else {
+                    self.add_piece(vec![p, o0, o1]);
+                }
+            }
+            LineJoin::Round => {
+                let mut piece = vec![p];
+                self.push_arc(&mut piece, p, (o0.y - p.y).atan2(o0.x - p.x), turn);
+                self.add_piece(piece);
+            }
+        }
+    }
+
+    fn push_arc(&self, points: &mut Vec<Point<f32>>, center: Point<f32>, start: f32, sweep: f32) {
+        let r = self.half_width;
+        let step = 2.0 * (1.0 - (self.tolerance / r).min(1.0)).acos();
+        let n = ((sweep.abs() / step).ceil() as usize).clamp(2, 1000);
+        for i in 0..=n {
+            let (sin, cos) = (start + sweep * i as f32 / n as f32).sin_cos();
+            points.push(Point::new(center.x + r * cos, center.y + r * sin));
+        }
+    }
+
+    fn add_piece(&mut self, mut piece: Vec<Point<f32>>) {
+        let area: f32 = (0..piece.len())
+            .map(|i| {
+                let (a, b) = (piece[i], piece[(i + 1) % piece.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum();
+        if area < 0.0 {
+            piece.reverse();
+        }
+        self.outline.add_polygon(&piece);
+    }
+
+    fn normal(&self, a: Point<f32>, b: Point<f32>) -> Point<f32> {
+        let d = unit(b - a);
+        Point::new(-d.y * self.half_width, d.x * self.half_width)
+    }
+}
+
+fn length(v: Point<f32>) -> f32 {
+    (v.x * v.x + v.y * v.y).sqrt()
+}
+
+fn unit(v: Point<f32>) -> Point<f32> {
+    let l = length(v);
+    if l > 0.0 {
+        Point::new(v.x / l, v.y / l)
+    } else {
+        Point::new(0.0, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::drawing::{FillRule, Rasterizer};
+    use crate::core::entity::rect::Rect;
+
+    fn area(path: &Path) -> f32 {
+        let mut rasterizer = Rasterizer::new(Rect::at(0, 0).of_size(64, 64));
+        rasterizer.add_path(path);
+        let mut total = 0.0;
+        rasterizer.for_each_pixel(FillRule::NonZero, |_, _, c| total += c);
+        total
+    }
+
+    fn stroke_area(points: &[(f32, f32)], style: StrokeStyle) -> f32 {
+        let mut path = Path::new();
+        path.move_to(points[0].0, points[0].1);
+        for &(x, y) in &points[1..] {
+            path.line_to(x, y);
+        }
+        area(&path.stroke(&style))
+    }
+
+    fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {} got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_arc_area(actual: f32, base: f32, radius: f32, sweep: f32) {
+        let exact = base + 0.5 * radius * radius * sweep;
+        let flattening = radius * sweep * FLATTEN_TOLERANCE;
+        assert!(
+            actual <= exact + 0.01 && actual >= exact - flattening,
+            "expected {} got {}",
+            exact,
+            actual
+        );
+    }
+
+    #[test]
+    fn caps_extend_the_segment() {
+        let line = [(10.0, 10.0), (30.0, 10.0)];
+        let style = |cap| StrokeStyle {
+            cap,
+            ..StrokeStyle::new(4.0)
+        };
+        assert_close(stroke_area(&line, style(LineCap::Butt)), 80.0, 0.01);
+        assert_close(stroke_area(&line, style(LineCap::Square)), 96.0, 0.01);
+        assert_arc_area(stroke_area(&line, style(LineCap::Round)), 80.0, 2.0, TAU);
+    }
+
+    #[test]
+    fn joins_fill_the_outer_corner() {
+        let corner = [(10.0, 10.0), (30.0, 10.0), (30.0, 30.0)];
+        let style = |join| StrokeStyle {
+            join,
+            ..StrokeStyle::new(4.0)
+        };
+        assert_close(stroke_area(&corner, style(LineJoin::Miter)), 160.0, 0.01);
+        assert_close(stroke_area(&corner, style(LineJoin::Bevel)), 158.0, 0.01);
+        assert_arc_area(
+            stroke_area(&corner, style(LineJoin::Round)),
+            156.0,
+            2.0,
+            PI / 2.0,
+        );
+    }
+
+    #[test]
+    fn miter_limit_falls_back_to_bevel() {
+        let spike = [(10.0, 40.0), (30.0, 10.0), (50.0, 40.0)];
+        let mitered = StrokeStyle::new(4.0);
+        let limited = StrokeStyle {
+            miter_limit: 1.0,
+            ..StrokeStyle::new(4.0)
+        };
+        let bevelled = StrokeStyle {
+            join: LineJoin::Bevel,
+            ..StrokeStyle::new(4.0)
+        };
+        assert_close(
+            stroke_area(&spike, limited),
+            stroke_area(&spike, bevelled.clone()),
+            0.01,
+        );
+        assert!(stroke_area(&spike, mitered) > stroke_area(&spike, bevelled) + 1.0);
+    }
+
+    #[test]
+    fn closed_rect_outline() {
+        let mut path = Path::new();
+        path.add_rect(10.0, 10.0, 20.0, 20.0);
+        assert_close(
+            area(&path.stroke(&StrokeStyle::new(2.0))),
+            22.0 * 22.0 - 18.0 * 18.0,
+            0.01,
+        );
+    }
+
+    #[test]
+    fn zero_width_stroke_is_empty() {
+        let line = [(10.0, 10.0), (30.0, 10.0)];
+        assert_eq!(stroke_area(&line, StrokeStyle::new(0.0)), 0.0);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::core::entity::point::Point;
+use crate::core::text::TextMetrics;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum HorizontalAnchor {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum VerticalAnchor {
+    #[default]
+    Top,
+    Middle,
+    Baseline,
+    Bottom,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct TextAnchor {
+    pub horizontal: HorizontalAnchor,
+    pub vertical: VerticalAnchor,
+}
+
+impl TextAnchor {
+    pub fn new(horizontal: HorizontalAnchor, vertical: VerticalAnchor) -> TextAnchor {
+        TextAnchor {
+            horizontal,
+            vertical,
+        }
+    }
+
+    pub fn origin(&self, metrics: &TextMetrics) -> Point<f32> {
+        let (x, y) = self.offset(metrics.advance_width(), metrics.ascent(), metrics.descent());
+        Point::new(x, y)
+    }
+
+    pub(crate) fn offset(&self, advance_width: f32, ascent: f32, descent: f32) -> (f32, f32) {
+        let x = match self.horizontal {
+            HorizontalAnchor::Start => 0.0,
+            HorizontalAnchor::Center => -advance_width / 2.0,
+            HorizontalAnchor::End => -advance_width,
+        };
+        let y = match self.vertical {
+            VerticalAnchor::Top => ascent,
+            VerticalAnchor::Middle => (ascent + descent) / 2.0,
+            VerticalAnchor::Baseline => 0.0,
+            VerticalAnchor::Bottom => descent,
+        };
+        (x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::{test_font, text_metrics};
+    use rusttype::Scale;
+
+    #[test]
+    fn offsets_follow_the_anchor() {
+        let offset =
+            |horizontal, vertical| TextAnchor::new(horizontal, vertical).offset(40.0, 12.0, -4.0);
+        assert_eq!(
+            offset(HorizontalAnchor::Start, VerticalAnchor::Top),
+            (0.0, 12.0)
+        );
+        assert_eq!(
+            offset(HorizontalAnchor::Center, VerticalAnchor::Middle),
+            (-20.0, 4.0)
+        );
+        assert_eq!(
+            offset(HorizontalAnchor::End, VerticalAnchor::Baseline),
+            (-40.0, 0.0)
+        );
+        assert_eq!(
+            offset(HorizontalAnchor::End, VerticalAnchor::Bottom),
+            (-40.0, -4.0)
+        );
+        assert_eq!(
+            TextAnchor::default(),
+            TextAnchor::new(HorizontalAnchor::Start, VerticalAnchor::Top)
+        );
+    }
+
+    #[test]
+    fn origin_uses_the_text_metrics() {
+        let font = test_font();
+        let metrics = text_metrics(Scale::uniform(20.0), &font, "Anchor");
+        let anchor = TextAnchor::new(HorizontalAnchor::Center, VerticalAnchor::Baseline);
+        assert_eq!(
+            anchor.origin(&metrics),
+            Point::new(-metrics.advance_width() / 2.0, 0.0)
+        );
+        let top = TextAnchor::new(HorizontalAnchor::End, VerticalAnchor::Top);
+        assert_eq!(
+            top.origin(&metrics),
+            Point::new(-metrics.advance_width(), metrics.ascent())
+        );
+    }
+}
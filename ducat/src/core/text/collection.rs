@@ -0,0 +1,394 @@
+use rusttype::{point, Font, GlyphId, Point, PositionedGlyph, Scale, ScaledGlyph};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum FontError {
+    Io(io::Error),
+    InvalidFont,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::Io(err) => write!(f, "failed to read font: {}", err),
+            FontError::InvalidFont => write!(f, "invalid font data"),
+        }
+    }
+}
+
+impl Error for FontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FontError::Io(err) => Some(err),
+            FontError::InvalidFont => None,
+        }
+    }
+}
+
+impl From<io::Error> for FontError {
+    fn from(err: io::Error) -> FontError {
+        FontError::Io(err)
+    }
+}
+
+pub fn load_font(bytes: Vec<u8>) -> Result<Font<'static>, FontError> {
+    Font::try_from_vec(bytes).ok_or(FontError::InvalidFont)
+}
+
+pub fn load_font_file<P: AsRef<Path>>(path: P) -> Result<Font<'static>, FontError> {
+    load_font(fs::read(path)?)
+}
+
+pub fn load_font_faces(bytes: &[u8]) -> Result<Vec<Font<'_>>, FontError> {
+    let count = face_count(bytes);
+    if count == 0 {
+        return Err(FontError::InvalidFont);
+    }
+    (0..count)
+        .map(|index| Font::try_from_bytes_and_index(bytes, index).ok_or(FontError::InvalidFont))
+        .collect()
+}
+
+pub fn load_font_faces_file<P: AsRef<Path>>(path: P) -> Result<Vec<Font<'static>>, FontError> {
+    let bytes = fs::read(path)?;
+    if face_count(&bytes) == 1 {
+        return Ok(vec![load_font(bytes)?]);
+    }
+    load_font_faces(&bytes)?;
+    load_font_faces(Box::leak(bytes.into_boxed_slice()))
+}
+
+fn face_count(bytes: &[u8]) -> u32 {
+    match bytes.get(..12) {
+        Some(header) if &header[..4] == b"ttcf" => {
+            let count = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+            count.min(((bytes.len() - 12) / 4) as u32)
+        }
+        _ => 1,
+    }
+}
+
+pub trait FontSource<'font> {
+    fn fonts(&self) -> &[Font<'font>];
+
+    fn font_index_for(&self, c: char) -> usize {
+        self.fonts()
+            .iter()
+            .position(|font| font.glyph(c).id().0 != 0)
+            .unwrap_or(0)
+    }
+
+    fn primary(&self) -> Option<&Font<'font>> {
+        self.fonts().first()
+    }
+}
+
+impl<'font> FontSource<'font> for Font<'font> {
+    fn fonts(&self) -> &[Font<'font>] {
+        std::slice::from_ref(self)
+    }
+
+    fn font_index_for(&self, _: char) -> usize {
+        0
+    }
+}
+
+pub(crate) struct ShapedGlyph<'font> {
+    pub byte_index: usize,
+    pub character: char,
+    pub font: usize,
+    pub glyph: ScaledGlyph<'font>,
+    pub x: f32,
+    pub advance: f32,
+}
+
+pub(crate) fn shape<'a, 'font: 'a, F: FontSource<'font>>(
+    source: &'a F,
+    scale: Scale,
+    text: &'a str,
+) -> impl Iterator<Item = ShapedGlyph<'font>> + 'a {
+    let fonts = source.fonts();
+    let text = if fonts.is_empty() { "" } else { text };
+    let mut caret = 0.0;
+    let mut last: Option<(usize, GlyphId)> = None;
+    text.char_indices().map(move |(byte_index, character)| {
+        let index = source.font_index_for(character);
+        let font = &fonts[index];
+        let glyph = font.glyph(character);
+        if let Some((previous_index, previous)) = last {
+            if previous_index == index {
+                caret += font.pair_kerning(scale, previous, glyph.id());
+            }
+        }
+        last = Some((index, glyph.id()));
+        let glyph = glyph.scaled(scale);
+        let advance = glyph.h_metrics().advance_width;
+        let x = caret;
+        caret += advance;
+        ShapedGlyph {
+            byte_index,
+            character,
+            font: index,
+            glyph,
+            x,
+            advance,
+        }
+    })
+}
+
+#[derive(Clone, Default)]
+pub struct FontCollection<'font> {
+    fonts: Vec<Font<'font>>,
+}
+
+impl<'font> FontCollection<'font> {
+    pub fn new() -> FontCollection<'font> {
+        FontCollection { fonts: Vec::new() }
+    }
+
+    pub fn with_font(mut self, font: Font<'font>) -> FontCollection<'font> {
+        self.push(font);
+        self
+    }
+
+    pub fn push(&mut self, font: Font<'font>) {
+        self.fonts.push(font);
+    }
+
+    pub fn fonts(&self) -> &[Font<'font>] {
+        &self.fonts
+    }
+
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    pub fn primary(&self) -> Option<&Font<'font>> {
+        self.fonts.first()
+    }
+
+    pub fn font_for(&self, c: char) -> Option<&Font<'font>> {
+        if self.fonts.is_empty() {
+            return None;
+        }
+        Some(&self.fonts[self.font_index_for(c)])
+    }
+
+    pub fn layout(
+        &self,
+        text: &str,
+        scale: Scale,
+        start: Point<f32>,
+    ) -> Vec<PositionedGlyph<'font>> {
+        if self.fonts.is_empty() {
+            return Vec::new();
+        }
+        shape(self, scale, text)
+            .map(|shaped| {
+                let position = point(start.x + shaped.x, start.y);
+                shaped.glyph.positioned(position)
+            })
+            .collect()
+    }
+}
+
+impl<'font> FontSource<'font> for FontCollection<'font> {
+    fn fonts(&self) -> &[Font<'font>] {
+        &self.fonts
+    }
+}
+
+impl FontCollection<'static> {
+    pub fn from_files<P: AsRef<Path>>(paths: &[P]) -> Result<FontCollection<'static>, FontError> {
+        let mut collection = FontCollection::new();
+        for path in paths {
+            for font in load_font_faces_file(path)? {
+                collection.push(font);
+            }
+        }
+        Ok(collection)
+    }
+
+    pub fn from_dir<P: AsRef<Path>>(
+        dir: P,
+    ) -> Result<(FontCollection<'static>, Vec<(PathBuf, FontError)>), FontError> {
+        let mut files = Vec::new();
+        let mut skipped = Vec::new();
+        collect_font_files(dir.as_ref(), &mut HashSet::new(), &mut files, &mut skipped)?;
+        files.sort();
+
+        let mut collection = FontCollection::new();
+        for file in files {
+            match load_font_faces_file(&file) {
+                Ok(fonts) => {
+                    for font in fonts {
+                        collection.push(font);
+                    }
+                }
+                Err(err) => skipped.push((file, err)),
+            }
+        }
+        skipped.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok((collection, skipped))
+    }
+}
+
+fn collect_font_files(
+    dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    files: &mut Vec<PathBuf>,
+    skipped: &mut Vec<(PathBuf, FontError)>,
+) -> io::Result<()> {
+    if !visited.insert(fs::canonicalize(dir)?) {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Err(err) = collect_font_files(&path, visited, files, skipped) {
+                skipped.push((path, FontError::Io(err)));
+            }
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ["ttf", "otf", "ttc"].contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::{layout_paragraph, test_font, text_metrics, ParagraphStyle};
+
+    const FONT: &[u8] = include_bytes!("../../../tests/data/fonts/DejaVuSans.ttf");
+
+    fn font_collection_file(face: &[u8], count: usize) -> Vec<u8> {
+        let header = 12 + 4 * count;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"ttcf");
+        bytes.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        bytes.extend_from_slice(&(count as u32).to_be_bytes());
+        for i in 0..count {
+            bytes.extend_from_slice(&((header + i * face.len()) as u32).to_be_bytes());
+        }
+        for _ in 0..count {
+            let base = bytes.len();
+            bytes.extend_from_slice(face);
+            let tables = u16::from_be_bytes([face[4], face[5]]) as usize;
+            for table in 0..tables {
+                let at = base + 12 + 16 * table + 8;
+                let offset =
+                    u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+                bytes[at..at + 4].copy_from_slice(&(offset + base as u32).to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn loads_every_face_of_a_collection_file() {
+        assert_eq!(load_font_faces(FONT).unwrap().len(), 1);
+        let bytes = font_collection_file(FONT, 3);
+        let faces = load_font_faces(&bytes).unwrap();
+        assert_eq!(faces.len(), 3);
+        for face in &faces {
+            assert_ne!(face.glyph('a').id().0, 0);
+        }
+        assert!(load_font_faces(b"not a font").is_err());
+    }
+
+    #[test]
+    fn from_dir_reports_skipped_files() {
+        let dir = std::env::temp_dir().join(format!("ducat-fonts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.ttf"), FONT).unwrap();
+        fs::write(dir.join("b.ttc"), font_collection_file(FONT, 2)).unwrap();
+        fs::write(dir.join("broken.otf"), b"not a font").unwrap();
+        fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+
+        let result = FontCollection::from_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let (collection, skipped) = result.unwrap();
+        assert_eq!(collection.len(), 3);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0, dir.join("broken.otf"));
+        assert!(matches!(skipped[0].1, FontError::InvalidFont));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn from_dir_visits_linked_directories_once() {
+        let dir = std::env::temp_dir().join(format!("ducat-font-links-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("nested").join("a.ttf"), FONT).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("nested").join("parent")).unwrap();
+
+        let result = FontCollection::from_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let (collection, skipped) = result.unwrap();
+        assert_eq!(collection.len(), 1);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn face_count_is_capped_by_the_header_size() {
+        let mut bytes = font_collection_file(FONT, 1);
+        bytes[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(face_count(&bytes) as usize <= (bytes.len() - 12) / 4);
+        assert!(load_font_faces(&bytes[..16]).is_err());
+    }
+
+    #[test]
+    fn empty_collection_lays_out_nothing() {
+        let empty = FontCollection::new();
+        let scale = Scale::uniform(20.0);
+        assert!(empty.font_for('a').is_none());
+        assert!(empty.layout("abc", scale, point(0.0, 0.0)).is_empty());
+        let layout = layout_paragraph(scale, &empty, "abc\ndef", &ParagraphStyle::new());
+        assert!(layout.lines().is_empty());
+        assert!(layout.glyphs().is_empty());
+        let metrics = text_metrics(scale, &empty, "abc");
+        assert_eq!(metrics.advance_width(), 0.0);
+        assert!(metrics.glyphs().is_empty());
+    }
+
+    #[test]
+    fn collection_and_font_lay_out_alike() {
+        let font = test_font();
+        let collection = FontCollection::new().with_font(font.clone());
+        let scale = Scale::uniform(20.0);
+        let text = "AVAWAY To fallback";
+        assert_eq!(
+            text_metrics(scale, &collection, text),
+            text_metrics(scale, &font, text)
+        );
+
+        let style = ParagraphStyle::new().with_max_width(80.0);
+        let positions = |layout: &crate::core::text::TextLayout| {
+            layout
+                .glyphs()
+                .iter()
+                .map(|glyph| (glyph.id(), glyph.position()))
+                .collect::<Vec<_>>()
+        };
+        let from_collection = layout_paragraph(scale, &collection, text, &style);
+        let from_font = layout_paragraph(scale, &font, text, &style);
+        assert_eq!(from_collection.lines(), from_font.lines());
+        assert_eq!(positions(&from_collection), positions(&from_font));
+        let glyphs = collection.layout(text, scale, point(0.0, 0.0));
+        assert_eq!(glyphs.len(), text.chars().count());
+    }
+}
@@ -0,0 +1,594 @@
+use crate::core::text::{shape, FontSource, ShapedGlyph};
+use rusttype::{point, PositionedGlyph, Scale};
+use std::ops::Range;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParagraphStyle {
+    max_width: Option<f32>,
+    wrap: bool,
+    line_height: f32,
+    align: TextAlign,
+    vertical_align: VerticalAlign,
+    max_lines: Option<usize>,
+    ellipsis: Option<String>,
+}
+
+impl ParagraphStyle {
+    pub fn new() -> ParagraphStyle {
+        ParagraphStyle {
+            max_width: None,
+            wrap: true,
+            line_height: 1.0,
+            align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
+            max_lines: None,
+            ellipsis: None,
+        }
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> ParagraphStyle {
+        self.max_width = Some(max_width.max(0.0));
+        self
+    }
+
+    pub fn with_wrap(mut self, wrap: bool) -> ParagraphStyle {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn with_line_height(mut self, line_height: f32) -> ParagraphStyle {
+        self.line_height = line_height;
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> ParagraphStyle {
+        self.align = align;
+        self
+    }
+
+    pub fn with_vertical_align(mut self, vertical_align: VerticalAlign) -> ParagraphStyle {
+        self.vertical_align = vertical_align;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> ParagraphStyle {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    pub fn with_ellipsis(mut self, ellipsis: &str) -> ParagraphStyle {
+        self.ellipsis = Some(ellipsis.to_string());
+        self
+    }
+
+    pub fn max_width(&self) -> Option<f32> {
+        self.max_width
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    pub fn align(&self) -> TextAlign {
+        self.align
+    }
+
+    pub fn vertical_align(&self) -> VerticalAlign {
+        self.vertical_align
+    }
+
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    pub fn ellipsis(&self) -> Option<&str> {
+        self.ellipsis.as_deref()
+    }
+}
+
+impl Default for ParagraphStyle {
+    fn default() -> ParagraphStyle {
+        ParagraphStyle::new()
+    }
+}
+
+struct LineSpan {
+    range: Range<usize>,
+    last_in_paragraph: bool,
+    truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    text: String,
+    range: Range<usize>,
+    glyphs: Range<usize>,
+    x: f32,
+    baseline: f32,
+    width: f32,
+}
+
+impl LayoutLine {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    pub fn glyphs(&self) -> Range<usize> {
+        self.glyphs.clone()
+    }
+
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn baseline(&self) -> f32 {
+        self.baseline
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TextLayout<'font> {
+    glyphs: Vec<PositionedGlyph<'font>>,
+    lines: Vec<LayoutLine>,
+    width: f32,
+    height: f32,
+    box_width: f32,
+    align: TextAlign,
+    vertical_align: VerticalAlign,
+    truncated: bool,
+}
+
+impl<'font> TextLayout<'font> {
+    pub fn glyphs(&self) -> &[PositionedGlyph<'font>] {
+        &self.glyphs
+    }
+
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn offset_in(&self, width: f32, height: f32) -> (f32, f32) {
+        let x = match self.align {
+            TextAlign::Center => (width - self.box_width) / 2.0,
+            TextAlign::Right => width - self.box_width,
+            TextAlign::Left | TextAlign::Justify => 0.0,
+        };
+        let y = match self.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => (height - self.height) / 2.0,
+            VerticalAlign::Bottom => height - self.height,
+        };
+        (x, y)
+    }
+}
+
+pub fn layout_paragraph<'font, F: FontSource<'font>>(
+    scale: Scale,
+    font: &F,
+    text: &str,
+    style: &ParagraphStyle,
+) -> TextLayout<'font> {
+    let v_metrics = match font.primary() {
+        Some(primary) => primary.v_metrics(scale),
+        None => {
+            return TextLayout {
+                glyphs: Vec::new(),
+                lines: Vec::new(),
+                width: 0.0,
+                height: 0.0,
+                box_width: style.max_width.unwrap_or(0.0),
+                align: style.align,
+                vertical_align: style.vertical_align,
+                truncated: false,
+            }
+        }
+    };
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for paragraph in text.split('\n') {
+        let paragraph = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+        let shaped: Vec<ShapedGlyph> = shape(font, scale, paragraph).collect();
+        if style.wrap {
+            let wrapped = wrap_paragraph(&shaped, paragraph.len(), style.max_width);
+            let count = wrapped.len();
+            for (i, range) in wrapped.into_iter().enumerate() {
+                lines.push(LineSpan {
+                    range: offset + range.start..offset + range.end,
+                    last_in_paragraph: i + 1 == count,
+                    truncated: false,
+                });
+            }
+        } else {
+            let width = shaped.last().map_or(0.0, |g| g.x + g.advance);
+            lines.push(LineSpan {
+                range: offset..offset + paragraph.len(),
+                last_in_paragraph: true,
+                truncated: style.max_width.is_some_and(|max_width| width > max_width),
+            });
+        }
+        offset += paragraph.len() + 1;
+    }
+
+    let mut truncated = false;
+    if let Some(max_lines) = style.max_lines {
+        if lines.len() > max_lines {
+            lines.truncate(max_lines);
+            truncated = true;
+            if let Some(last) = lines.last_mut() {
+                last.truncated |= style.ellipsis.is_some();
+            }
+        }
+    }
+    truncated |= lines.iter().any(|line| line.truncated);
+
+    let texts: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            if line.truncated {
+                truncate_with_ellipsis(
+                    scale,
+                    font,
+                    &text[line.range.clone()],
+                    style.ellipsis.as_deref().unwrap_or(""),
+                    style.max_width,
+                )
+            } else {
+                text[line.range.clone()].to_string()
+            }
+        })
+        .collect();
+
+    let widths: Vec<f32> = texts.iter().map(|t| measure(scale, font, t)).collect();
+    let width = widths.iter().cloned().fold(0.0, f32::max);
+    let box_width = style.max_width.unwrap_or(width);
+
+    let advance = style.line_height * (v_metrics.ascent - v_metrics.descent + v_metrics.line_gap);
+
+    let count = lines.len();
+    let mut glyphs = Vec::new();
+    let mut layout_lines = Vec::with_capacity(count);
+    for (i, (line, text)) in lines.into_iter().zip(texts).enumerate() {
+        let line_width = widths[i];
+        let spaces = text.chars().filter(|c| c.is_whitespace()).count();
+        let justify = style.align == TextAlign::Justify
+            && !line.last_in_paragraph
+            && !line.truncated
+            && spaces > 0;
+        let (x, stretch) = match style.align {
+            TextAlign::Left => (0.0, 0.0),
+            TextAlign::Center => ((box_width - line_width) / 2.0, 0.0),
+            TextAlign::Right => (box_width - line_width, 0.0),
+            TextAlign::Justify if justify => (0.0, (box_width - line_width) / spaces as f32),
+            TextAlign::Justify => (0.0, 0.0),
+        };
+        let baseline = v_metrics.ascent + i as f32 * advance;
+
+        let first = glyphs.len();
+        let mut stretched = 0.0;
+        for shaped in shape(font, scale, &text) {
+            let position = point(x + shaped.x + stretched, baseline);
+            glyphs.push(shaped.glyph.positioned(position));
+            if shaped.character.is_whitespace() {
+                stretched += stretch;
+            }
+        }
+
+        layout_lines.push(LayoutLine {
+            text,
+            range: line.range,
+            glyphs: first..glyphs.len(),
+            x,
+            baseline,
+            width: if justify { box_width } else { line_width },
+        });
+    }
+
+    let height = match layout_lines.len() {
+        0 => 0.0,
+        n => v_metrics.ascent - v_metrics.descent + (n - 1) as f32 * advance,
+    };
+
+    TextLayout {
+        glyphs,
+        lines: layout_lines,
+        width,
+        height,
+        box_width,
+        align: style.align,
+        vertical_align: style.vertical_align,
+        truncated,
+    }
+}
+
+pub(crate) fn measure<'font, F: FontSource<'font>>(scale: Scale, font: &F, text: &str) -> f32 {
+    shape(font, scale, text)
+        .last()
+        .map_or(0.0, |shaped| shaped.x + shaped.advance)
+}
+
+fn words(glyphs: &[ShapedGlyph]) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, glyph) in glyphs.iter().enumerate() {
+        match (glyph.character.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..glyphs.len());
+    }
+    words
+}
+
+fn wrap_paragraph(glyphs: &[ShapedGlyph], len: usize, max_width: Option<f32>) -> Vec<Range<usize>> {
+    let max_width = max_width.unwrap_or(f32::INFINITY);
+    let fits = |start: usize, end: usize| {
+        let last = &glyphs[end - 1];
+        last.x + last.advance - glyphs[start].x <= max_width
+    };
+    let byte = |i: usize| glyphs.get(i).map_or(len, |glyph| glyph.byte_index);
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut end = None;
+    for word in words(glyphs) {
+        if let Some(e) = end {
+            if !fits(start, word.end) {
+                lines.push(byte(start)..byte(e));
+                start = word.start;
+                end = None;
+            }
+        }
+        if end.is_none() {
+            while word.end - start > 1 && !fits(start, word.end) {
+                let split = break_word(start, word.end, |b| fits(start, b));
+                lines.push(byte(start)..byte(split));
+                start = split;
+            }
+        }
+        end = Some(word.end);
+    }
+    lines.push(byte(start)..byte(end.unwrap_or(start)));
+    lines
+}
+
+fn break_word(start: usize, end: usize, fits: impl Fn(usize) -> bool) -> usize {
+    let mut split = start + 1;
+    while split < end && fits(split + 1) {
+        split += 1;
+    }
+    split
+}
+
+fn truncate_with_ellipsis<'font, F: FontSource<'font>>(
+    scale: Scale,
+    font: &F,
+    text: &str,
+    ellipsis: &str,
+    max_width: Option<f32>,
+) -> String {
+    let text = text.trim_end();
+    let max_width = match max_width {
+        Some(max_width) => max_width,
+        None => return text.to_string() + ellipsis,
+    };
+    let ellipsis_width = measure(scale, font, ellipsis);
+    let ellipsis_first = ellipsis.chars().next().map(|c| {
+        let index = font.font_index_for(c);
+        (index, font.fonts()[index].glyph(c).id())
+    });
+
+    let mut end = 0;
+    for shaped in shape(font, scale, text) {
+        if shaped.character.is_whitespace() {
+            continue;
+        }
+        let kerning = match ellipsis_first {
+            Some((index, first)) if index == shaped.font => {
+                font.fonts()[index].pair_kerning(scale, shaped.glyph.id(), first)
+            }
+            _ => 0.0,
+        };
+        if shaped.x + shaped.advance + kerning + ellipsis_width > max_width {
+            break;
+        }
+        end = shaped.byte_index + shaped.character.len_utf8();
+    }
+    text[..end].to_string() + ellipsis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::test_font;
+
+    const TEXT: &str = "the quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn overflowing_line_gets_ellipsis() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(80.0)
+            .with_wrap(false)
+            .with_ellipsis("...");
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert_eq!(layout.lines().len(), 1);
+        assert!(layout.is_truncated());
+        let line = &layout.lines()[0];
+        assert!(line.text().ends_with("..."));
+        assert!(line.text().len() > 3);
+        assert!(line.width() <= 80.0, "width {}", line.width());
+    }
+
+    #[test]
+    fn fitting_line_keeps_its_text() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(200.0)
+            .with_ellipsis("...");
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, "lazy dog", &style);
+        assert!(!layout.is_truncated());
+        assert_eq!(layout.lines()[0].text(), "lazy dog");
+    }
+
+    #[test]
+    fn max_lines_truncates_with_ellipsis() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(100.0)
+            .with_max_lines(2)
+            .with_ellipsis("...");
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert_eq!(layout.lines().len(), 2);
+        assert!(layout.is_truncated());
+        assert!(!layout.lines()[0].text().ends_with("..."));
+        let last = &layout.lines()[1];
+        assert!(last.text().ends_with("..."));
+        assert!(last.width() <= 100.0, "width {}", last.width());
+    }
+
+    #[test]
+    fn wraps_without_ellipsis() {
+        let font = test_font();
+        let style = ParagraphStyle::new().with_max_width(100.0);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert!(layout.lines().len() > 1);
+        assert!(!layout.is_truncated());
+        for line in layout.lines() {
+            assert!(line.width() <= 100.0, "width {}", line.width());
+        }
+        let words: Vec<&str> = layout
+            .lines()
+            .iter()
+            .flat_map(|line| line.text().split_whitespace())
+            .collect();
+        assert_eq!(words.join(" "), TEXT);
+    }
+
+    fn line_texts(layout: &TextLayout) -> Vec<String> {
+        layout
+            .lines()
+            .iter()
+            .map(|line| line.text().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn max_lines_without_ellipsis_keeps_wrapped_lines() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(120.0)
+            .with_max_lines(2);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert_eq!(line_texts(&layout), vec!["the quick", "brown fox"]);
+        assert!(layout.is_truncated());
+
+        let wrapped = ParagraphStyle::new().with_max_width(120.0);
+        let full = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &wrapped);
+        assert_eq!(line_texts(&full)[..2], line_texts(&layout)[..]);
+    }
+
+    #[test]
+    fn max_lines_ellipsis_stays_within_the_kept_line() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(120.0)
+            .with_max_lines(2)
+            .with_ellipsis("...");
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        let texts = line_texts(&layout);
+        assert_eq!(texts[0], "the quick");
+        assert!(texts[1].ends_with("..."));
+        assert!("brown fox".starts_with(texts[1].trim_end_matches("...")));
+    }
+
+    #[test]
+    fn ellipsis_does_not_disable_wrapping() {
+        let font = test_font();
+        let style = ParagraphStyle::new()
+            .with_max_width(120.0)
+            .with_ellipsis("...");
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert!(layout.lines().len() > 1);
+        assert!(!layout.is_truncated());
+        assert!(layout
+            .lines()
+            .iter()
+            .all(|line| !line.text().ends_with("...")));
+    }
+
+    #[test]
+    fn long_words_break_between_characters() {
+        let font = test_font();
+        let word = "abcdefghijklmnopqrstuvwxyz";
+        let style = ParagraphStyle::new().with_max_width(60.0);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, word, &style);
+        assert!(layout.lines().len() > 2);
+        assert_eq!(line_texts(&layout).concat(), word);
+        for line in layout.lines() {
+            assert!(line.width() <= 60.0, "width {}", line.width());
+        }
+
+        let narrow = ParagraphStyle::new().with_max_width(1.0);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, "ab", &narrow);
+        assert_eq!(line_texts(&layout), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn unwrapped_lines_without_ellipsis_are_clipped() {
+        let font = test_font();
+        let style = ParagraphStyle::new().with_max_width(80.0).with_wrap(false);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, "lazy dog\n", &style);
+        assert_eq!(line_texts(&layout), vec!["lazy dog", ""]);
+        let layout = layout_paragraph(Scale::uniform(20.0), &font, TEXT, &style);
+        assert!(layout.is_truncated());
+        assert!(TEXT.starts_with(layout.lines()[0].text()));
+        assert!(layout.lines()[0].width() <= 80.0);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::core::entity::bounds::Bounds;
+use crate::core::entity::point::Point;
+use crate::core::text::{shape, FontSource};
+use rusttype::{Scale, VMetrics};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GlyphMetrics {
+    pub character: char,
+    pub byte_index: usize,
+    pub x: f32,
+    pub advance_width: f32,
+    pub ink_bounds: Option<Bounds>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMetrics {
+    advance_width: f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    ink_bounds: Option<Bounds>,
+    glyphs: Vec<GlyphMetrics>,
+    carets: Vec<f32>,
+}
+
+impl TextMetrics {
+    pub fn advance_width(&self) -> f32 {
+        self.advance_width
+    }
+
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    pub fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    pub fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+    pub fn ink_bounds(&self) -> Option<Bounds> {
+        self.ink_bounds
+    }
+
+    pub fn logical_bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new(0.0, -self.ascent),
+            Point::new(self.advance_width, -self.descent),
+        )
+    }
+
+    pub fn glyphs(&self) -> &[GlyphMetrics] {
+        &self.glyphs
+    }
+
+    pub fn carets(&self) -> &[f32] {
+        &self.carets
+    }
+
+    pub fn caret_offset(&self, index: usize) -> f32 {
+        self.carets[index.min(self.carets.len() - 1)]
+    }
+
+    pub fn caret_index_at(&self, x: f32) -> usize {
+        let after = self.carets.partition_point(|&caret| caret < x);
+        if after == 0 {
+            0
+        } else if after == self.carets.len() {
+            self.carets.len() - 1
+        } else if x - self.carets[after - 1] < self.carets[after] - x {
+            after - 1
+        } else {
+            after
+        }
+    }
+
+    pub fn glyph_index_at(&self, x: f32) -> Option<usize> {
+        self.glyphs
+            .iter()
+            .position(|g| x >= g.x && x < g.x + g.advance_width)
+    }
+}
+
+pub fn text_metrics<'font, F: FontSource<'font>>(
+    scale: Scale,
+    font: &F,
+    text: &str,
+) -> TextMetrics {
+    let v_metrics = font.primary().map_or(
+        VMetrics {
+            ascent: 0.0,
+            descent: 0.0,
+            line_gap: 0.0,
+        },
+        |primary| primary.v_metrics(scale),
+    );
+    let mut glyphs = Vec::new();
+    let mut carets = vec![0.0];
+    let mut ink_bounds: Option<Bounds> = None;
+
+    let mut caret = 0.0;
+    for shaped in shape(font, scale, text) {
+        let x = shaped.x;
+        let bounds = shaped.glyph.exact_bounding_box().map(|bb| {
+            Bounds::new(
+                Point::new(x + bb.min.x, bb.min.y),
+                Point::new(x + bb.max.x, bb.max.y),
+            )
+        });
+        if let Some(bounds) = bounds {
+            ink_bounds = Some(ink_bounds.map_or(bounds, |ink| ink.union(bounds)));
+        }
+
+        glyphs.push(GlyphMetrics {
+            character: shaped.character,
+            byte_index: shaped.byte_index,
+            x,
+            advance_width: shaped.advance,
+            ink_bounds: bounds,
+        });
+        caret = x + shaped.advance;
+        carets.push(caret);
+    }
+
+    TextMetrics {
+        advance_width: caret,
+        ascent: v_metrics.ascent,
+        descent: v_metrics.descent,
+        line_gap: v_metrics.line_gap,
+        ink_bounds,
+        glyphs,
+        carets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::test_font;
+
+    #[test]
+    fn carets_follow_the_glyph_advances() {
+        let font = test_font();
+        let metrics = text_metrics(Scale::uniform(20.0), &font, "a\u{e9}b");
+        let carets = metrics.carets();
+        assert_eq!(carets.len(), 4);
+        assert_eq!(carets[0], 0.0);
+        assert!(carets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(carets[3], metrics.advance_width());
+        for (glyph, &caret) in metrics.glyphs().iter().zip(&carets[1..]) {
+            assert_eq!(caret, glyph.x + glyph.advance_width);
+        }
+        let bytes: Vec<usize> = metrics.glyphs().iter().map(|g| g.byte_index).collect();
+        assert_eq!(bytes, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn caret_lookup_snaps_to_the_nearest_boundary() {
+        let font = test_font();
+        let metrics = text_metrics(Scale::uniform(20.0), &font, "abc");
+        let carets = metrics.carets().to_vec();
+        assert_eq!(metrics.caret_offset(1), carets[1]);
+        assert_eq!(metrics.caret_offset(99), carets[3]);
+        assert_eq!(metrics.caret_index_at(-5.0), 0);
+        assert_eq!(metrics.caret_index_at(carets[1] - 0.5), 1);
+        assert_eq!(metrics.caret_index_at(carets[1] + 0.5), 1);
+        assert_eq!(metrics.caret_index_at(carets[3] + 5.0), 3);
+        assert_eq!(metrics.glyph_index_at(carets[1] + 0.5), Some(1));
+        assert_eq!(metrics.glyph_index_at(carets[3] + 0.5), None);
+    }
+
+    #[test]
+    fn empty_text_has_a_single_caret() {
+        let font = test_font();
+        let metrics = text_metrics(Scale::uniform(20.0), &font, "");
+        assert_eq!(metrics.carets(), &[0.0]);
+        assert_eq!(metrics.caret_offset(3), 0.0);
+        assert_eq!(metrics.caret_index_at(10.0), 0);
+        assert!(metrics.ink_bounds().is_none());
+    }
+}
@@ -0,0 +1,33 @@
+mod anchor;
+pub use self::anchor::{HorizontalAnchor, TextAnchor, VerticalAnchor};
+
+mod layout;
+pub(crate) use self::layout::measure;
+pub use self::layout::{
+    layout_paragraph, LayoutLine, ParagraphStyle, TextAlign, TextLayout, VerticalAlign,
+};
+
+mod metrics;
+pub use self::metrics::{text_metrics, GlyphMetrics, TextMetrics};
+
+mod outline;
+pub use self::outline::{glyph_outline, text_outline};
+
+mod path_text;
+pub use self::path_text::{text_path_outline, PathTextStyle};
+
+mod style;
+pub use self::style::{TextBackground, TextOutline, TextShadow, TextStyle};
+
+mod collection;
+pub use self::collection::{
+    load_font, load_font_faces, load_font_faces_file, load_font_file, FontCollection, FontError,
+    FontSource,
+};
+pub(crate) use self::collection::{shape, ShapedGlyph};
+
+#[cfg(test)]
+pub(crate) fn test_font() -> rusttype::Font<'static> {
+    rusttype::Font::try_from_bytes(include_bytes!("../../../tests/data/fonts/DejaVuSans.ttf"))
+        .expect("test font should parse")
+}
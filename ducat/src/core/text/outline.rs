@@ -0,0 +1,73 @@
+use crate::core::entity::point::Point;
+use crate::core::entity::transform::Transform2D;
+use crate::core::path::Path;
+use crate::core::text::{shape, FontSource};
+use rusttype::{OutlineBuilder, Scale, ScaledGlyph};
+
+struct PathOutline<'a> {
+    path: &'a mut Path,
+    transform: Transform2D,
+}
+
+impl PathOutline<'_> {
+    fn apply(&self, x: f32, y: f32) -> Point<f32> {
+        self.transform.apply(Point::new(x, y))
+    }
+}
+
+impl OutlineBuilder for PathOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.apply(x, y);
+        self.path.move_to(p.x, p.y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.apply(x, y);
+        self.path.line_to(p.x, p.y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (c, p) = (self.apply(x1, y1), self.apply(x, y));
+        self.path.quad_to(c.x, c.y, p.x, p.y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (c1, c2, p) = (self.apply(x1, y1), self.apply(x2, y2), self.apply(x, y));
+        self.path.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y);
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
+pub fn glyph_outline(glyph: &ScaledGlyph, origin: Point<f32>) -> Path {
+    let mut path = Path::new();
+    append_glyph_outline(
+        &mut path,
+        glyph,
+        &Transform2D::translate(origin.x, origin.y),
+    );
+    path
+}
+
+pub(crate) fn append_glyph_outline(path: &mut Path, glyph: &ScaledGlyph, transform: &Transform2D) {
+    glyph.build_outline(&mut PathOutline {
+        path,
+        transform: *transform,
+    });
+}
+
+pub fn text_outline<'font, F: FontSource<'font>>(
+    scale: Scale,
+    font: &F,
+    text: &str,
+    origin: Point<f32>,
+) -> Path {
+    let mut path = Path::new();
+    for shaped in shape(font, scale, text) {
+        let transform = Transform2D::translate(origin.x + shaped.x, origin.y);
+        append_glyph_outline(&mut path, &shaped.glyph, &transform);
+    }
+    path
+}
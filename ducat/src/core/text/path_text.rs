@@ -0,0 +1,193 @@
+use crate::core::entity::point::Point;
+use crate::core::entity::transform::Transform2D;
+use crate::core::path::{Path, FLATTEN_TOLERANCE};
+use crate::core::text::outline::append_glyph_outline;
+use crate::core::text::{measure, shape, FontSource, HorizontalAnchor, TextAnchor, VerticalAnchor};
+use rusttype::Scale;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PathTextStyle {
+    offset: f32,
+    anchor: TextAnchor,
+}
+
+impl PathTextStyle {
+    pub fn new() -> PathTextStyle {
+        PathTextStyle {
+            offset: 0.0,
+            anchor: TextAnchor::new(HorizontalAnchor::Start, VerticalAnchor::Baseline),
+        }
+    }
+
+    pub fn with_offset(mut self, offset: f32) -> PathTextStyle {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: TextAnchor) -> PathTextStyle {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    pub fn anchor(&self) -> TextAnchor {
+        self.anchor
+    }
+}
+
+impl Default for PathTextStyle {
+    fn default() -> PathTextStyle {
+        PathTextStyle::new()
+    }
+}
+
+struct Track {
+    segments: Vec<(Point<f32>, Point<f32>, f32)>,
+    length: f32,
+}
+
+impl Track {
+    fn new(path: &Path) -> Track {
+        let mut segments = Vec::new();
+        let mut length = 0.0;
+        for polyline in path.flatten(FLATTEN_TOLERANCE) {
+            let mut points = polyline.points.clone();
+            if polyline.closed {
+                points.push(points[0]);
+            }
+            for w in points.windows(2) {
+                let d = (w[1].x - w[0].x).hypot(w[1].y - w[0].y);
+                if d > 0.0 {
+                    segments.push((w[0], w[1], length));
+                    length += d;
+                }
+            }
+        }
+        Track { segments, length }
+    }
+
+    fn segment_at(&self, distance: f32) -> (Point<f32>, Point<f32>, f32) {
+        let i = self
+            .segments
+            .partition_point(|&(_, _, start)| start <= distance)
+            .max(1);
+        self.segments[i - 1]
+    }
+
+    fn point_at(&self, distance: f32) -> Point<f32> {
+        let (a, b, start) = self.segment_at(distance);
+        let d = (b.x - a.x).hypot(b.y - a.y);
+        let t = ((distance - start) / d).clamp(0.0, 1.0);
+        Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+    }
+
+    fn angle_at(&self, distance: f32) -> f32 {
+        let (a, b, _) = self.segment_at(distance);
+        (b.y - a.y).atan2(b.x - a.x)
+    }
+}
+
+pub fn text_path_outline<'font, F: FontSource<'font>>(
+    scale: Scale,
+    font: &F,
+    text: &str,
+    path: &Path,
+    style: &PathTextStyle,
+) -> Path {
+    let mut outline = Path::new();
+    let track = Track::new(path);
+    if track.segments.is_empty() {
+        return outline;
+    }
+
+    let v_metrics = match font.primary() {
+        Some(primary) => primary.v_metrics(scale),
+        None => return outline,
+    };
+    let width = measure(scale, font, text);
+    let (_, dy) = style
+        .anchor
+        .offset(width, v_metrics.ascent, v_metrics.descent);
+    let origin = style.offset
+        + match style.anchor.horizontal {
+            HorizontalAnchor::Start => 0.0,
+            HorizontalAnchor::Center => (track.length - width) / 2.0,
+            HorizontalAnchor::End => track.length - width,
+        };
+
+    for shaped in shape(font, scale, text) {
+        let advance = shaped.advance;
+        let middle = origin + shaped.x + advance / 2.0;
+        if middle < 0.0 || middle > track.length {
+            continue;
+        }
+
+        let start = track.point_at((middle - advance / 2.0).max(0.0));
+        let end = track.point_at((middle + advance / 2.0).min(track.length));
+        let angle = if (end.x - start.x).hypot(end.y - start.y) > advance * 0.25 {
+            (end.y - start.y).atan2(end.x - start.x)
+        } else {
+            track.angle_at(middle)
+        };
+        let position = track.point_at(middle);
+        let transform = Transform2D::translate(-advance / 2.0, dy)
+            .then(&Transform2D::rotate(angle))
+            .then(&Transform2D::translate(position.x, position.y));
+        append_glyph_outline(&mut outline, &shaped.glyph, &transform);
+    }
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::text::{test_font, text_outline};
+
+    fn assert_aligned(horizontal: HorizontalAnchor, x: impl Fn(f32, f32) -> f32) {
+        let font = test_font();
+        let scale = Scale::uniform(24.0);
+        let text = "along";
+        let mut path = Path::new();
+        path.move_to(10.0, 50.0);
+        path.line_to(210.0, 50.0);
+        let length = path.length(FLATTEN_TOLERANCE);
+        assert_eq!(length, 200.0);
+
+        let style =
+            PathTextStyle::new().with_anchor(TextAnchor::new(horizontal, VerticalAnchor::Baseline));
+        let outline = text_path_outline(scale, &font, text, &path, &style);
+        let origin = Point::new(10.0 + x(length, measure(scale, &font, text)), 50.0);
+        let expected = text_outline(scale, &font, text, origin);
+
+        assert_eq!(outline.sub_paths().count(), expected.sub_paths().count());
+        let (actual, expected) = (outline.bounds().unwrap(), expected.bounds().unwrap());
+        for (a, e) in [
+            (actual.min.x, expected.min.x),
+            (actual.min.y, expected.min.y),
+            (actual.max.x, expected.max.x),
+            (actual.max.y, expected.max.y),
+        ] {
+            assert!((a - e).abs() < 0.01, "expected {} got {}", e, a);
+        }
+    }
+
+    #[test]
+    fn start_aligns_to_path_start() {
+        assert_aligned(HorizontalAnchor::Start, |_, _| 0.0);
+    }
+
+    #[test]
+    fn center_aligns_to_path_middle() {
+        assert_aligned(HorizontalAnchor::Center, |length, width| {
+            (length - width) / 2.0
+        });
+    }
+
+    #[test]
+    fn end_aligns_to_path_end() {
+        assert_aligned(HorizontalAnchor::End, |length, width| length - width);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::core::text::TextAnchor;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextOutline<P> {
+    pub width: f32,
+    pub color: P,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextShadow<P> {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur_radius: f32,
+    pub color: P,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextBackground<P> {
+    pub padding: f32,
+    pub color: P,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextStyle<P> {
+    anchor: TextAnchor,
+    outline: Option<TextOutline<P>>,
+    shadow: Option<TextShadow<P>>,
+    background: Option<TextBackground<P>>,
+}
+
+impl<P> TextStyle<P> {
+    pub fn new() -> TextStyle<P> {
+        TextStyle {
+            anchor: TextAnchor::default(),
+            outline: None,
+            shadow: None,
+            background: None,
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: TextAnchor) -> TextStyle<P> {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_outline(mut self, width: f32, color: P) -> TextStyle<P> {
+        self.outline = Some(TextOutline { width, color });
+        self
+    }
+
+    pub fn with_shadow(
+        mut self,
+        offset_x: f32,
+        offset_y: f32,
+        blur_radius: f32,
+        color: P,
+    ) -> TextStyle<P> {
+        self.shadow = Some(TextShadow {
+            offset_x,
+            offset_y,
+            blur_radius,
+            color,
+        });
+        self
+    }
+
+    pub fn with_background(mut self, padding: f32, color: P) -> TextStyle<P> {
+        self.background = Some(TextBackground { padding, color });
+        self
+    }
+
+    pub fn anchor(&self) -> TextAnchor {
+        self.anchor
+    }
+
+    pub fn outline(&self) -> Option<&TextOutline<P>> {
+        self.outline.as_ref()
+    }
+
+    pub fn shadow(&self) -> Option<&TextShadow<P>> {
+        self.shadow.as_ref()
+    }
+
+    pub fn background(&self) -> Option<&TextBackground<P>> {
+        self.background.as_ref()
+    }
+}
+
+impl<P> Default for TextStyle<P> {
+    fn default() -> TextStyle<P> {
+        TextStyle::new()
+    }
+}